@@ -0,0 +1,49 @@
+/// Place the diagnostics at the edges and corners of the screen, clear of a safe area
+use bevy::prelude::*;
+
+use bevy_screen_diagnostics::{
+    DiagnosticsAnchor, SafeArea, ScreenDiagnostics, ScreenDiagnosticsPlugin,
+    ScreenDiagnosticsTheme, ScreenFrameDiagnosticsPlugin,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ScreenDiagnosticsPlugin {
+            anchor: Some(DiagnosticsAnchor::TopLeft),
+            margin: Vec2::splat(10.0),
+            theme: ScreenDiagnosticsTheme::dark(),
+            ..default()
+        })
+        .add_plugins(ScreenFrameDiagnosticsPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (move_diagnostics, scale_ui))
+        .run();
+}
+
+fn setup(mut commands: Commands, mut diags: ResMut<ScreenDiagnostics>) {
+    // need a camera to display the UI
+    commands.spawn(Camera2d);
+    // keep clear of a notch at the top of the screen
+    diags.set_safe_area(SafeArea {
+        top: 30.0,
+        ..default()
+    });
+}
+
+// move the diagnostics to the next anchor with tab
+fn move_diagnostics(input: Res<ButtonInput<KeyCode>>, mut diags: ResMut<ScreenDiagnostics>) {
+    if input.just_pressed(KeyCode::Tab) {
+        diags.cycle_anchor();
+    }
+}
+
+// change the UI scale with the up and down arrow keys, the margins scale with it
+fn scale_ui(input: Res<ButtonInput<KeyCode>>, mut ui_scale: ResMut<UiScale>) {
+    if input.just_pressed(KeyCode::ArrowUp) {
+        ui_scale.0 += 0.25;
+    }
+    if input.just_pressed(KeyCode::ArrowDown) {
+        ui_scale.0 = (ui_scale.0 - 0.25).max(0.25);
+    }
+}
@@ -0,0 +1,51 @@
+/// Draw a translucent panel behind the diagnostics, to keep them readable on bright scenes
+use bevy::prelude::*;
+
+use bevy_screen_diagnostics::{
+    DiagnosticsBackground, ScreenDiagnosticsPlugin, ScreenDiagnosticsTheme,
+    ScreenEntityDiagnosticsPlugin, ScreenFrameDiagnosticsPlugin,
+};
+
+fn main() {
+    App::new()
+        // a bright scene, where the text is hard to read without a background
+        .insert_resource(ClearColor(Color::srgb(0.9, 0.9, 0.85)))
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ScreenDiagnosticsPlugin {
+            theme: ScreenDiagnosticsTheme {
+                background: DiagnosticsBackground {
+                    color: Color::srgba(0.0, 0.0, 0.0, 0.6),
+                    padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    border_color: Color::srgb(0.4, 0.4, 0.4),
+                    border_radius: BorderRadius::all(Val::Px(6.0)),
+                },
+                ..default()
+            },
+            ..default()
+        })
+        .add_plugins(ScreenFrameDiagnosticsPlugin)
+        .add_plugins(ScreenEntityDiagnosticsPlugin)
+        .add_systems(Startup, setup_camera)
+        .add_systems(Update, opacity)
+        .run();
+}
+
+// need a camera to display the UI
+fn setup_camera(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+// change the opacity of the background with the up and down arrow keys
+fn opacity(input: Res<ButtonInput<KeyCode>>, mut theme: ResMut<ScreenDiagnosticsTheme>) {
+    let step = match (
+        input.just_pressed(KeyCode::ArrowUp),
+        input.just_pressed(KeyCode::ArrowDown),
+    ) {
+        (true, false) => 0.1,
+        (false, true) => -0.1,
+        _ => return,
+    };
+    let alpha = (theme.background.color.alpha() + step).clamp(0.0, 1.0);
+    theme.background.color.set_alpha(alpha);
+}
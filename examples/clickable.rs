@@ -0,0 +1,27 @@
+/// Click the name of a diagnostic to show its history graph, hover it to see its path
+use bevy::prelude::*;
+
+use bevy_screen_diagnostics::{
+    DiagnosticsClick, DiagnosticsLayout, ScreenDiagnosticsPlugin, ScreenDiagnosticsTheme,
+    ScreenEntityDiagnosticsPlugin, ScreenFrameDiagnosticsPlugin,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ScreenDiagnosticsPlugin {
+            click: Some(DiagnosticsClick::ToggleGraph),
+            layout: DiagnosticsLayout::Table { value_width: 60.0 },
+            theme: ScreenDiagnosticsTheme::dark(),
+            ..default()
+        })
+        .add_plugins(ScreenFrameDiagnosticsPlugin)
+        .add_plugins(ScreenEntityDiagnosticsPlugin)
+        .add_systems(Startup, setup_camera)
+        .run();
+}
+
+// need a camera to display the UI
+fn setup_camera(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
@@ -0,0 +1,75 @@
+/// Display diagnostics in labels floating above entities
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+};
+
+use bevy_screen_diagnostics::{
+    Aggregate, EntityDiagnostics, ScreenDiagnosticsPlugin, ScreenDiagnosticsTheme,
+    ScreenFrameDiagnosticsPlugin,
+};
+
+// each agent has its own diagnostic
+const AGENTS: [DiagnosticPath; 3] = [
+    DiagnosticPath::const_new("agent/0/speed"),
+    DiagnosticPath::const_new("agent/1/speed"),
+    DiagnosticPath::const_new("agent/2/speed"),
+];
+
+#[derive(Component)]
+struct Agent(usize);
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins)
+        .add_plugins(ScreenDiagnosticsPlugin {
+            theme: ScreenDiagnosticsTheme::dark(),
+            ..default()
+        })
+        .add_plugins(ScreenFrameDiagnosticsPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, move_agents);
+    for path in AGENTS {
+        app.register_diagnostic(Diagnostic::new(path).with_suffix("px/s"));
+    }
+    app.run();
+}
+
+fn setup(mut commands: Commands) {
+    // need a camera to display the UI
+    commands.spawn(Camera2d);
+
+    for (i, path) in AGENTS.into_iter().enumerate() {
+        let mut diagnostics = EntityDiagnostics::default().with_offset(Vec3::Y * 30.0);
+        diagnostics
+            .add("speed", path)
+            .aggregate(Aggregate::MovingAverage(10));
+        commands.spawn((
+            Agent(i),
+            Sprite::from_color(Color::srgb(0.3, 0.6, 0.9), Vec2::splat(40.0)),
+            Transform::default(),
+            diagnostics,
+        ));
+    }
+}
+
+fn move_agents(
+    mut agents: Query<(&Agent, &mut Transform)>,
+    mut diagnostics: Diagnostics,
+    time: Res<Time>,
+) {
+    let t = time.elapsed_secs();
+    for (agent, mut transform) in &mut agents {
+        let speed = 1.0 + agent.0 as f32 * 0.5;
+        let previous = transform.translation;
+        transform.translation = Vec3::new(
+            (t * speed).cos() * 200.0,
+            (t * speed * 0.7).sin() * 150.0,
+            0.0,
+        );
+        let distance = previous.distance(transform.translation);
+        diagnostics.add_measurement(&AGENTS[agent.0], || {
+            (distance / time.delta_secs().max(f32::EPSILON)) as f64
+        });
+    }
+}
@@ -0,0 +1,33 @@
+/// Show a graph of the frametimes next to the frame diagnostics
+use bevy::prelude::*;
+
+use bevy_screen_diagnostics::{
+    FrameBudget, ScreenDiagnosticsPlugin, ScreenFrameDiagnosticsPlugin, ScreenFrameGraphPlugin,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ScreenDiagnosticsPlugin::default())
+        .add_plugins(ScreenFrameDiagnosticsPlugin)
+        .add_plugins(ScreenFrameGraphPlugin {
+            budgets: vec![
+                FrameBudget {
+                    frame_time: 1000.0 / 144.0,
+                    color: Color::srgb(0.4, 0.6, 1.0),
+                },
+                FrameBudget {
+                    frame_time: 1000.0 / 60.0,
+                    color: Color::srgb(1.0, 0.2, 0.2),
+                },
+            ],
+            ..default()
+        })
+        .add_systems(Startup, setup_camera)
+        .run();
+}
+
+// need a camera to display the UI
+fn setup_camera(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
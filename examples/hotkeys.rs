@@ -0,0 +1,31 @@
+/// Control the diagnostics with the keyboard
+///
+/// F9 toggles the panel, F10 cycles the presets, F11 toggles the names and F12 moves the panel.
+use bevy::prelude::*;
+
+use bevy_screen_diagnostics::{
+    DiagnosticsKeys, ScreenDiagnostics, ScreenDiagnosticsPlugin, ScreenEntityDiagnosticsPlugin,
+    ScreenFrameDiagnosticsPlugin,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ScreenDiagnosticsPlugin {
+            keys: DiagnosticsKeys::function_keys(),
+            ..default()
+        })
+        .add_plugins(ScreenFrameDiagnosticsPlugin)
+        .add_plugins(ScreenEntityDiagnosticsPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut diags: ResMut<ScreenDiagnostics>) {
+    // need a camera to display the UI
+    commands.spawn(Camera2d);
+
+    // verbosity levels, all diagnostics are shown after the last one
+    diags.add_preset(["fps"]);
+    diags.add_preset(["fps", "ms/frame"]);
+}
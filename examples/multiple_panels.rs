@@ -0,0 +1,57 @@
+/// Display diagnostics in multiple independent panels
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+};
+
+use bevy_screen_diagnostics::{
+    DiagnosticsAnchor, DiagnosticsLayout, ScreenDiagnostics, ScreenDiagnosticsPlugin,
+    ScreenDiagnosticsTheme, ScreenFrameDiagnosticsPlugin, Unit,
+};
+
+// the marker type of the second panel
+struct NetworkPanel;
+
+const BYTES_SENT: DiagnosticPath = DiagnosticPath::const_new("network/bytes_sent");
+const PING: DiagnosticPath = DiagnosticPath::const_new("network/ping");
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .register_diagnostic(Diagnostic::new(BYTES_SENT))
+        .register_diagnostic(Diagnostic::new(PING).with_suffix("ms"))
+        // the default panel, used by the builtin plugins like `ScreenFrameDiagnosticsPlugin`
+        .add_plugins(ScreenDiagnosticsPlugin {
+            anchor: Some(DiagnosticsAnchor::TopLeft),
+            theme: ScreenDiagnosticsTheme::dark(),
+            ..default()
+        })
+        .add_plugins(
+            ScreenDiagnosticsPlugin {
+                anchor: Some(DiagnosticsAnchor::TopRight),
+                layout: DiagnosticsLayout::Table { value_width: 80.0 },
+                // the theme is shared, but the font size can differ per panel
+                font_size: Some(16.0),
+                ..default()
+            }
+            .with_panel::<NetworkPanel>(),
+        )
+        .add_plugins(ScreenFrameDiagnosticsPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, fake_network)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut network: ResMut<ScreenDiagnostics<NetworkPanel>>) {
+    // need a camera to display the UI
+    commands.spawn(Camera2d);
+
+    network.add("sent", BYTES_SENT).unit(Unit::Bytes);
+    network.add("ping", PING);
+}
+
+fn fake_network(mut diagnostics: Diagnostics, time: Res<Time>) {
+    let t = time.elapsed_secs_f64();
+    diagnostics.add_measurement(&BYTES_SENT, || 1500.0 * t);
+    diagnostics.add_measurement(&PING, || 40.0 + 10.0 * t.sin());
+}
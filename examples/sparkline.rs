@@ -0,0 +1,31 @@
+/// Show a history graph next to the frametime
+use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+
+use bevy_screen_diagnostics::{
+    ScreenDiagnostics, ScreenDiagnosticsPlugin, ScreenFrameDiagnosticsPlugin, Sparkline,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ScreenDiagnosticsPlugin::default())
+        .add_plugins(ScreenFrameDiagnosticsPlugin)
+        .add_systems(Startup, (setup_camera, setup_graph))
+        .run();
+}
+
+// need a camera to display the UI
+fn setup_camera(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn setup_graph(mut diags: ResMut<ScreenDiagnostics>) {
+    diags
+        .add("frametime", FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .format(|v| format!("{v:.2}"))
+        .graph(Sparkline {
+            samples: 60,
+            size: Vec2::new(120.0, 18.0),
+            color: Some(Color::srgb(0.3, 0.9, 0.4)),
+        });
+}
@@ -0,0 +1,76 @@
+/// Display a separate panel in each half of a split screen
+use bevy::{
+    diagnostic::EntityCountDiagnosticsPlugin, prelude::*, render::camera::Viewport,
+    window::PrimaryWindow,
+};
+
+use bevy_screen_diagnostics::{
+    DiagnosticsAnchor, ScreenDiagnostics, ScreenDiagnosticsPlugin, ScreenFrameDiagnosticsPlugin,
+};
+
+// the marker type of the panel in the right half
+struct RightPanel;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EntityCountDiagnosticsPlugin)
+        .add_plugins(ScreenDiagnosticsPlugin {
+            anchor: Some(DiagnosticsAnchor::TopLeft),
+            ..default()
+        })
+        .add_plugins(
+            ScreenDiagnosticsPlugin {
+                anchor: Some(DiagnosticsAnchor::TopLeft),
+                ..default()
+            }
+            .with_panel::<RightPanel>(),
+        )
+        .add_plugins(ScreenFrameDiagnosticsPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut left: ResMut<ScreenDiagnostics>,
+    mut right: ResMut<ScreenDiagnostics<RightPanel>>,
+) {
+    let size = window.physical_size();
+    let half = UVec2::new(size.x / 2, size.y);
+
+    // one camera for each half of the window
+    let left_camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                viewport: Some(Viewport {
+                    physical_position: UVec2::ZERO,
+                    physical_size: half,
+                    ..default()
+                }),
+                ..default()
+            },
+        ))
+        .id();
+    let right_camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                order: 1,
+                viewport: Some(Viewport {
+                    physical_position: UVec2::new(half.x, 0),
+                    physical_size: half,
+                    ..default()
+                }),
+                clear_color: ClearColorConfig::Custom(Color::srgb(0.1, 0.1, 0.2)),
+                ..default()
+            },
+        ))
+        .id();
+
+    left.set_target_camera(Some(left_camera));
+    right.set_target_camera(Some(right_camera));
+    right.add("entities", EntityCountDiagnosticsPlugin::ENTITY_COUNT);
+}
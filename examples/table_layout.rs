@@ -0,0 +1,25 @@
+/// Show one diagnostic per row, with the values in a column
+use bevy::prelude::*;
+
+use bevy_screen_diagnostics::{
+    DiagnosticsLayout, ScreenDiagnosticsPlugin, ScreenEntityDiagnosticsPlugin,
+    ScreenFrameDiagnosticsPlugin,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ScreenDiagnosticsPlugin {
+            layout: DiagnosticsLayout::Table { value_width: 80.0 },
+            ..default()
+        })
+        .add_plugins(ScreenFrameDiagnosticsPlugin)
+        .add_plugins(ScreenEntityDiagnosticsPlugin)
+        .add_systems(Startup, setup_camera)
+        .run();
+}
+
+// need a camera to display the UI
+fn setup_camera(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
@@ -0,0 +1,55 @@
+/// Switch between the theme presets at runtime
+use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+
+use bevy_screen_diagnostics::{
+    Aggregate, ScreenDiagnostics, ScreenDiagnosticsPlugin, ScreenDiagnosticsTheme,
+    ScreenEntityDiagnosticsPlugin, ScreenFrameDiagnosticsPlugin,
+};
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::srgb(0.3, 0.4, 0.5)))
+        .add_plugins(DefaultPlugins)
+        .add_plugins(ScreenDiagnosticsPlugin {
+            theme: ScreenDiagnosticsTheme::dark(),
+            ..default()
+        })
+        .add_plugins(ScreenFrameDiagnosticsPlugin)
+        .add_plugins(ScreenEntityDiagnosticsPlugin)
+        .add_systems(Startup, (setup_camera, setup))
+        .add_systems(Update, switch_theme)
+        .run();
+}
+
+// need a camera to display the UI
+fn setup_camera(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn setup(mut diags: ResMut<ScreenDiagnostics>) {
+    // colored with the palette of the current theme
+    diags
+        .add("frame", FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .aggregate(Aggregate::MovingAverage(5))
+        .palette_thresholds([1000.0 / 60.0, 1000.0 / 30.0]);
+}
+
+// switch between the theme presets with space
+fn switch_theme(
+    input: Res<ButtonInput<KeyCode>>,
+    mut theme: ResMut<ScreenDiagnosticsTheme>,
+    mut current: Local<usize>,
+) {
+    if !input.just_pressed(KeyCode::Space) {
+        return;
+    }
+    let presets = [
+        ScreenDiagnosticsTheme::dark,
+        ScreenDiagnosticsTheme::light,
+        ScreenDiagnosticsTheme::high_contrast,
+        ScreenDiagnosticsTheme::color_blind_safe,
+        ScreenDiagnosticsTheme::default,
+    ];
+    *current = (*current + 1) % presets.len();
+    *theme = presets[*current]();
+}
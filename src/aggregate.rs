@@ -0,0 +1,365 @@
+use std::time::Duration;
+
+use bevy::{diagnostic::Diagnostic, platform::time::Instant, prelude::*};
+
+/// Aggregaes which can be used for displaying Diagnostics.
+///
+/// The windowed aggregates like [Aggregate::Max] or [Aggregate::MaxOver] use all stored measurements
+/// when fewer than the requested amount or duration are available,
+/// for example when the window is longer than the history kept by the [Diagnostic].
+#[derive(Copy, Clone, Default, Debug, Reflect)]
+pub enum Aggregate {
+    /// The latest [Diagnostic::value]
+    #[default]
+    Value,
+    /// The [Diagnostic::average] of all recorded diagnostic measurements.
+    #[allow(dead_code)]
+    Average,
+    /// A moving average over the n last diagnostic measurements.
+    MovingAverage(usize),
+    /// The smallest of the n last diagnostic measurements.
+    Min(usize),
+    /// The largest of the n last diagnostic measurements.
+    Max(usize),
+    /// The median of the n last diagnostic measurements.
+    Median(usize),
+    /// The percentile (0.0 - 100.0) of the n last diagnostic measurements.
+    ///
+    /// `Percentile(99.0, 300)` is the value which 99% of the last 300 measurements are below.
+    Percentile(f64, usize),
+    /// The average of the lowest percent (0.0 - 100.0) of the n last diagnostic measurements.
+    ///
+    /// `PercentLow(1.0, 1000)` on the framerate is the "1% low" fps.
+    PercentLow(f64, usize),
+    /// The average of the highest percent (0.0 - 100.0) of the n last diagnostic measurements.
+    ///
+    /// `PercentHigh(1.0, 1000)` on the frametime is the frametime of the slowest 1% of frames.
+    PercentHigh(f64, usize),
+    /// A moving average over the measurements taken within the duration before the latest one.
+    ///
+    /// Unlike [Aggregate::MovingAverage], this does not depend on how often the diagnostic is measured.
+    MovingAverageOver(Duration),
+    /// The smallest of the measurements taken within the duration before the latest one.
+    MinOver(Duration),
+    /// The largest of the measurements taken within the duration before the latest one.
+    MaxOver(Duration),
+    /// An exponential moving average, where each new measurement moves the value by `alpha` (0.0 - 1.0) towards it.
+    ///
+    /// An `alpha` outside of the range is clamped, a NaN `alpha` displays the latest measurement.
+    /// The average is kept between updates of the display, so it is smoothed over all measurements, not just the stored history.
+    Exponential {
+        /// The weight of each new measurement. Lower values are smoother but react slower.
+        alpha: f64,
+    },
+    /// An exponential moving average which reacts to changes in roughly the given duration, independent of how often the diagnostic is measured.
+    ///
+    /// After the duration 63% of a sudden change is reflected, after 3 times the duration 95% are.
+    /// Unlike [Diagnostic::smoothed] this is independent of the smoothing factor of the diagnostic.
+    Smoothed(Duration),
+    /// The change per second between the two latest measurements.
+    ///
+    /// Useful for diagnostics which count up, like bytes sent, to display them as a rate
+    /// with a format like ``|v| format!("{v:.1}/s")``.
+    /// Requires a history length of at least 2 on the [Diagnostic].
+    RatePerSecond,
+}
+
+/// A custom reduction of the history of a [Diagnostic] to the value which is displayed.
+///
+/// Registered with [DiagnosticsTextBuilder::aggregate_with](crate::DiagnosticsTextBuilder::aggregate_with).
+/// This is implemented for closures taking a `&Diagnostic`.
+///
+/// ```rust
+///# use bevy::diagnostic::Diagnostic;
+///# use bevy_screen_diagnostics::DiagnosticAggregator;
+/// /// The mean of the stored history without the highest and lowest 10%.
+/// struct TrimmedMean;
+///
+/// impl DiagnosticAggregator for TrimmedMean {
+///     fn aggregate(&mut self, diagnostic: &Diagnostic) -> Option<f64> {
+///         let mut values: Vec<f64> = diagnostic.values().copied().collect();
+///         values.sort_by(f64::total_cmp);
+///         let trim = values.len() / 10;
+///         let trimmed = &values[trim..values.len() - trim];
+///         (!trimmed.is_empty()).then(|| trimmed.iter().sum::<f64>() / trimmed.len() as f64)
+///     }
+/// }
+/// ```
+pub trait DiagnosticAggregator: Send + Sync + 'static {
+    /// Calculate the displayed value. When `None` is returned, the display is not updated.
+    ///
+    /// Called on every update of the on-screen diagnostics, so this can keep state between updates.
+    fn aggregate(&mut self, diagnostic: &Diagnostic) -> Option<f64>;
+}
+
+impl<F> DiagnosticAggregator for F
+where
+    F: FnMut(&Diagnostic) -> Option<f64> + Send + Sync + 'static,
+{
+    fn aggregate(&mut self, diagnostic: &Diagnostic) -> Option<f64> {
+        self(diagnostic)
+    }
+}
+
+/// Aggregate state which is kept between updates, used by [Aggregate::Exponential] and [Aggregate::Smoothed].
+#[derive(Default, Debug, Clone)]
+pub(crate) struct AggregateState {
+    value: Option<f64>,
+    last: Option<Instant>,
+}
+
+impl AggregateState {
+    /// Fold all measurements since the last update into the state, weighted by `alpha(time since previous measurement)`.
+    ///
+    /// A NaN `alpha` takes the measurement as is, so it can't poison the state.
+    fn update(&mut self, diagnostic: &Diagnostic, alpha: impl Fn(Duration) -> f64) -> Option<f64> {
+        for measurement in diagnostic.measurements() {
+            if self.last.is_some_and(|last| measurement.time <= last)
+                || !measurement.value.is_finite()
+            {
+                continue;
+            }
+            self.value = Some(match (self.value, self.last) {
+                (Some(value), Some(last)) => {
+                    let alpha = alpha(measurement.time.duration_since(last));
+                    let alpha = match alpha.is_nan() {
+                        true => 1.0,
+                        false => alpha.clamp(0.0, 1.0),
+                    };
+                    value + alpha * (measurement.value - value)
+                }
+                _ => measurement.value,
+            });
+            self.last = Some(measurement.time);
+        }
+        self.value
+    }
+}
+
+impl Aggregate {
+    /// Calculate the aggregated value from the history of a [Diagnostic].
+    pub(crate) fn apply(&self, diagnostic: &Diagnostic, state: &mut AggregateState) -> Option<f64> {
+        match *self {
+            Aggregate::Value => diagnostic.value(),
+            Aggregate::Average => diagnostic.average(),
+            Aggregate::MovingAverage(count) => mean(last_n(diagnostic, count)),
+            Aggregate::Min(count) => last_n(diagnostic, count).reduce(f64::min),
+            Aggregate::Max(count) => last_n(diagnostic, count).reduce(f64::max),
+            Aggregate::Median(count) => percentile(&sorted_last_n(diagnostic, count), 50.0),
+            Aggregate::Percentile(percent, count) => {
+                percentile(&sorted_last_n(diagnostic, count), percent)
+            }
+            Aggregate::PercentLow(percent, count) => {
+                let sorted = sorted_last_n(diagnostic, count);
+                mean(sorted[..percent_len(sorted.len(), percent)].iter().copied())
+            }
+            Aggregate::PercentHigh(percent, count) => {
+                let sorted = sorted_last_n(diagnostic, count);
+                mean(
+                    sorted[sorted.len() - percent_len(sorted.len(), percent)..]
+                        .iter()
+                        .copied(),
+                )
+            }
+            Aggregate::MovingAverageOver(window) => mean(within(diagnostic, window)),
+            Aggregate::MinOver(window) => within(diagnostic, window).reduce(f64::min),
+            Aggregate::MaxOver(window) => within(diagnostic, window).reduce(f64::max),
+            Aggregate::Exponential { alpha } => state.update(diagnostic, |_| alpha),
+            Aggregate::Smoothed(duration) => state.update(diagnostic, |delta| {
+                1.0 - (-delta.as_secs_f64() / duration.as_secs_f64()).exp()
+            }),
+            Aggregate::RatePerSecond => {
+                let (previous, latest) = diagnostic
+                    .measurements()
+                    .filter(|m| m.value.is_finite())
+                    .fold((None, None), |(_, latest), m| (latest, Some(m)));
+                let (previous, latest) = (previous?, latest?);
+                let seconds = latest.time.duration_since(previous.time).as_secs_f64();
+                (seconds > 0.0).then(|| (latest.value - previous.value) / seconds)
+            }
+        }
+    }
+}
+
+/// The last n finite values of the diagnostic, or all of them if fewer are stored.
+fn last_n(diagnostic: &Diagnostic, count: usize) -> impl Iterator<Item = f64> + '_ {
+    let skip = diagnostic.history_len().saturating_sub(count);
+    diagnostic
+        .values()
+        .skip(skip)
+        .copied()
+        .filter(|v| v.is_finite())
+}
+
+/// The finite values measured within the duration before the latest measurement.
+fn within(diagnostic: &Diagnostic, window: Duration) -> impl Iterator<Item = f64> + '_ {
+    let latest = diagnostic.measurement().map(|m| m.time);
+    diagnostic
+        .measurements()
+        .skip_while(move |m| latest.is_some_and(|latest| latest.duration_since(m.time) > window))
+        .map(|m| m.value)
+        .filter(|v| v.is_finite())
+}
+
+fn sorted_last_n(diagnostic: &Diagnostic, count: usize) -> Vec<f64> {
+    let mut values: Vec<f64> = last_n(diagnostic, count).collect();
+    values.sort_by(f64::total_cmp);
+    values
+}
+
+/// Linearly interpolated percentile of sorted values.
+fn percentile(sorted: &[f64], percent: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = (percent.clamp(0.0, 100.0) / 100.0) * last as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * rank.fract())
+}
+
+/// The amount of values making up the given percentage, at least one if there are any.
+fn percent_len(len: usize, percent: f64) -> usize {
+    let n = (len as f64 * percent.clamp(0.0, 100.0) / 100.0).ceil() as usize;
+    n.clamp(len.min(1), len)
+}
+
+/// The mean of the values, without collecting them.
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (count, sum) = values.fold((0, 0.0), |(count, sum), v| (count + 1, sum + v));
+    (count > 0).then(|| sum / count as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::diagnostic::{DiagnosticMeasurement, DiagnosticPath};
+
+    use super::*;
+
+    fn diagnostic(values: &[f64]) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(DiagnosticPath::const_new("test"));
+        let start = Instant::now();
+        for (i, value) in values.iter().enumerate() {
+            diagnostic.add_measurement(DiagnosticMeasurement {
+                time: start + Duration::from_millis(i as u64),
+                value: *value,
+            });
+        }
+        diagnostic
+    }
+
+    fn apply(agg: Aggregate, values: &[f64]) -> Option<f64> {
+        agg.apply(&diagnostic(values), &mut AggregateState::default())
+    }
+
+    /// Add a measurement one millisecond after the latest one.
+    fn push(diagnostic: &mut Diagnostic, value: f64) {
+        let time = diagnostic.measurement().unwrap().time + Duration::from_millis(1);
+        diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+    }
+
+    fn assert_close(value: Option<f64>, expected: f64) {
+        let value = value.unwrap();
+        assert!((value - expected).abs() < 1e-9, "{value} != {expected}");
+    }
+
+    #[test]
+    fn percentile_interpolates() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(percentile(&sorted, 0.0), Some(1.0));
+        assert_eq!(percentile(&sorted, 50.0), Some(2.5));
+        assert_eq!(percentile(&sorted, 100.0), Some(4.0));
+        assert_eq!(percentile(&sorted, 150.0), Some(4.0));
+        assert_eq!(percentile(&[7.0], 99.0), Some(7.0));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn percent_len_rounds_up() {
+        assert_eq!(percent_len(1000, 1.0), 10);
+        assert_eq!(percent_len(10, 15.0), 2);
+        assert_eq!(percent_len(10, 0.0), 1);
+        assert_eq!(percent_len(10, 200.0), 10);
+        assert_eq!(percent_len(1, 1.0), 1);
+        assert_eq!(percent_len(0, 1.0), 0);
+    }
+
+    #[test]
+    fn percent_low_and_high() {
+        let values: Vec<f64> = (1..=10).map(f64::from).collect();
+        assert_eq!(apply(Aggregate::PercentLow(20.0, 10), &values), Some(1.5));
+        assert_eq!(apply(Aggregate::PercentHigh(20.0, 10), &values), Some(9.5));
+    }
+
+    #[test]
+    fn moving_average_skips_non_finite() {
+        let values = [1.0, 2.0, f64::INFINITY, 4.0, 6.0];
+        assert_eq!(apply(Aggregate::MovingAverage(3), &values), Some(5.0));
+        // shorter history than the window
+        assert_eq!(apply(Aggregate::MovingAverage(100), &[2.0, 4.0]), Some(3.0));
+    }
+
+    #[test]
+    fn empty_history() {
+        for agg in [
+            Aggregate::Min(5),
+            Aggregate::Max(5),
+            Aggregate::Median(5),
+            Aggregate::Percentile(99.0, 5),
+            Aggregate::PercentLow(1.0, 5),
+            Aggregate::PercentHigh(1.0, 5),
+        ] {
+            assert_eq!(apply(agg, &[]), None, "{agg:?} without measurements");
+            assert_eq!(apply(agg, &[f64::NAN; 3]), None, "{agg:?} with NaN");
+        }
+        assert_eq!(apply(Aggregate::MovingAverage(0), &[1.0, 2.0]), None);
+        assert_eq!(apply(Aggregate::PercentLow(1.0, 0), &[1.0, 2.0]), None);
+        assert_eq!(apply(Aggregate::PercentHigh(1.0, 0), &[1.0, 2.0]), None);
+    }
+
+    #[test]
+    fn one_sample() {
+        for agg in [
+            Aggregate::MovingAverage(5),
+            Aggregate::Min(5),
+            Aggregate::Max(5),
+            Aggregate::Median(5),
+            Aggregate::Percentile(99.0, 5),
+            Aggregate::PercentLow(1.0, 5),
+            Aggregate::PercentHigh(1.0, 5),
+        ] {
+            assert_eq!(apply(agg, &[3.0]), Some(3.0), "{agg:?}");
+        }
+    }
+
+    #[test]
+    fn exponential_keeps_state_between_updates() {
+        let agg = Aggregate::Exponential { alpha: 0.5 };
+        let mut state = AggregateState::default();
+        let mut diagnostic = diagnostic(&[0.0, 4.0]);
+        assert_eq!(agg.apply(&diagnostic, &mut state), Some(2.0));
+        // measurements which were already folded in are not counted again
+        assert_eq!(agg.apply(&diagnostic, &mut state), Some(2.0));
+        push(&mut diagnostic, 6.0);
+        assert_eq!(agg.apply(&diagnostic, &mut state), Some(4.0));
+    }
+
+    #[test]
+    fn exponential_nan_alpha() {
+        let agg = Aggregate::Exponential { alpha: f64::NAN };
+        let mut state = AggregateState::default();
+        let mut diagnostic = diagnostic(&[0.0, 4.0]);
+        assert_eq!(agg.apply(&diagnostic, &mut state), Some(4.0));
+        push(&mut diagnostic, 6.0);
+        assert_eq!(agg.apply(&diagnostic, &mut state), Some(6.0));
+    }
+
+    #[test]
+    fn smoothed_time_constant() {
+        let agg = Aggregate::Smoothed(Duration::from_millis(1));
+        let mut state = AggregateState::default();
+        let mut diagnostic = diagnostic(&[0.0, 1.0]);
+        // 63% of the change after one time constant
+        assert_close(agg.apply(&diagnostic, &mut state), 1.0 - (-1.0f64).exp());
+        push(&mut diagnostic, 1.0);
+        // 86% after two, over two updates
+        assert_close(agg.apply(&diagnostic, &mut state), 1.0 - (-2.0f64).exp());
+    }
+}
@@ -0,0 +1,223 @@
+use std::time::Duration;
+
+use bevy::{
+    diagnostic::DiagnosticPath, ecs::system::SystemParam, platform::time::Instant, prelude::*,
+};
+
+/// Which side of an [AlertThreshold] the value has to cross to raise the alert.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum AlertBound {
+    /// Alert when the value drops below the threshold.
+    Below,
+    /// Alert when the value rises above the threshold.
+    Above,
+}
+
+/// When a [DiagnosticAlert] is raised, see [DiagnosticsTextBuilder::alert](crate::DiagnosticsTextBuilder::alert).
+///
+/// Example: ``AlertThreshold::below(30.0).with_duration(Duration::from_secs(2))`` on the fps
+/// raises an alert once the framerate stayed below 30 for 2 seconds.
+#[derive(Clone, Copy, Debug, Reflect)]
+pub struct AlertThreshold {
+    /// Which side of the threshold raises the alert.
+    pub bound: AlertBound,
+    /// The threshold compared against the aggregated value.
+    pub value: f64,
+    /// How far the value has to move back past the threshold before the alert is cleared. Default: 0.0
+    pub hysteresis: f64,
+    /// How long the value has to stay past the threshold before the alert is raised. Default: 0s
+    pub duration: Duration,
+}
+
+impl AlertThreshold {
+    /// Alert when the value drops below `value`.
+    pub fn below(value: f64) -> Self {
+        Self {
+            bound: AlertBound::Below,
+            value,
+            hysteresis: 0.0,
+            duration: Duration::ZERO,
+        }
+    }
+
+    /// Alert when the value rises above `value`.
+    pub fn above(value: f64) -> Self {
+        Self {
+            bound: AlertBound::Above,
+            ..Self::below(value)
+        }
+    }
+
+    /// Set how far the value has to move back past the threshold before the alert is cleared.
+    pub fn with_hysteresis(self, hysteresis: f64) -> Self {
+        Self { hysteresis, ..self }
+    }
+
+    /// Set how long the value has to stay past the threshold before the alert is raised.
+    pub fn with_duration(self, duration: Duration) -> Self {
+        Self { duration, ..self }
+    }
+
+    fn is_past(&self, v: f64) -> bool {
+        match self.bound {
+            AlertBound::Below => v < self.value,
+            AlertBound::Above => v > self.value,
+        }
+    }
+
+    fn is_cleared(&self, v: f64) -> bool {
+        match self.bound {
+            AlertBound::Below => v >= self.value + self.hysteresis,
+            AlertBound::Above => v <= self.value - self.hysteresis,
+        }
+    }
+}
+
+/// Whether a [DiagnosticAlert] was raised or cleared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum AlertState {
+    /// The value stayed past the threshold for the configured duration.
+    Raised,
+    /// The value moved back past the threshold and the hysteresis.
+    Cleared,
+}
+
+/// Raised when a diagnostic crosses an [AlertThreshold], and again when it is cleared.
+///
+/// This is both sent as an [Event] and triggered for global observers,
+/// and is raised whether or not the diagnostic is currently displayed.
+///
+/// ```rust
+///# use bevy::prelude::*;
+///# use bevy_screen_diagnostics::{AlertState, DiagnosticAlert};
+/// fn on_alert(trigger: Trigger<DiagnosticAlert>) {
+///     if trigger.state == AlertState::Raised {
+///         warn!("{} is at {:.1}", trigger.name, trigger.value);
+///     }
+/// }
+///
+///# fn main() {
+/// App::new().add_observer(on_alert);
+///# }
+/// ```
+#[derive(Event, Clone, Debug)]
+pub struct DiagnosticAlert {
+    /// The name of the diagnostic as shown on screen.
+    pub name: String,
+    /// The [DiagnosticPath] of the diagnostic.
+    pub path: DiagnosticPath,
+    /// The aggregated value which raised or cleared the alert.
+    pub value: f64,
+    /// The threshold which was crossed.
+    pub threshold: AlertThreshold,
+    /// Whether the alert was raised or cleared.
+    pub state: AlertState,
+}
+
+/// An [AlertThreshold] and its state between updates.
+#[derive(Clone, Debug)]
+pub(crate) struct Alert {
+    threshold: AlertThreshold,
+    since: Option<Instant>,
+    raised: bool,
+}
+
+impl Alert {
+    pub(crate) fn new(threshold: AlertThreshold) -> Self {
+        Self {
+            threshold,
+            since: None,
+            raised: false,
+        }
+    }
+
+    /// Check the value measured at `time`, returning the new state when it changed.
+    pub(crate) fn update(&mut self, v: f64, time: Instant) -> Option<AlertState> {
+        if self.raised {
+            if self.threshold.is_cleared(v) {
+                self.raised = false;
+                self.since = None;
+                return Some(AlertState::Cleared);
+            }
+        } else if self.threshold.is_past(v) {
+            let since = *self.since.get_or_insert(time);
+            if time.duration_since(since) >= self.threshold.duration {
+                self.raised = true;
+                return Some(AlertState::Raised);
+            }
+        } else {
+            self.since = None;
+        }
+        None
+    }
+
+    pub(crate) fn threshold(&self) -> AlertThreshold {
+        self.threshold
+    }
+}
+
+/// Sends [DiagnosticAlert]s both as events and as triggers for observers.
+#[derive(SystemParam)]
+pub(crate) struct AlertWriter<'w, 's> {
+    commands: Commands<'w, 's>,
+    events: EventWriter<'w, DiagnosticAlert>,
+}
+
+impl AlertWriter<'_, '_> {
+    pub(crate) fn write(&mut self, alert: DiagnosticAlert) {
+        self.commands.trigger(alert.clone());
+        self.events.write(alert);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raise_after_duration_and_clear_past_hysteresis() {
+        let start = Instant::now();
+        let at = |secs: f64| start + Duration::from_secs_f64(secs);
+        let mut alert = Alert::new(
+            AlertThreshold::below(30.0)
+                .with_hysteresis(5.0)
+                .with_duration(Duration::from_secs(2)),
+        );
+
+        // not raised before the duration passed
+        assert_eq!(alert.update(20.0, at(0.0)), None);
+        assert_eq!(alert.update(20.0, at(1.0)), None);
+        assert_eq!(alert.update(20.0, at(2.0)), Some(AlertState::Raised));
+        // not raised again while still past the threshold
+        assert_eq!(alert.update(10.0, at(3.0)), None);
+        // back past the threshold, but within the hysteresis
+        assert_eq!(alert.update(32.0, at(4.0)), None);
+        assert_eq!(alert.update(35.0, at(5.0)), Some(AlertState::Cleared));
+        assert_eq!(alert.update(40.0, at(6.0)), None);
+    }
+
+    #[test]
+    fn timer_resets_on_recovery() {
+        let start = Instant::now();
+        let at = |secs: f64| start + Duration::from_secs_f64(secs);
+        let mut alert =
+            Alert::new(AlertThreshold::above(16.7).with_duration(Duration::from_secs(2)));
+
+        assert_eq!(alert.update(20.0, at(0.0)), None);
+        // recovered before the duration
+        assert_eq!(alert.update(10.0, at(1.5)), None);
+        assert_eq!(alert.update(20.0, at(2.0)), None);
+        // would have been raised without the reset
+        assert_eq!(alert.update(20.0, at(3.0)), None);
+        assert_eq!(alert.update(20.0, at(4.0)), Some(AlertState::Raised));
+    }
+
+    #[test]
+    fn raise_immediately_without_duration() {
+        let mut alert = Alert::new(AlertThreshold::above(1.0));
+        let now = Instant::now();
+        assert_eq!(alert.update(1.0, now), None);
+        assert_eq!(alert.update(1.5, now), Some(AlertState::Raised));
+        assert_eq!(alert.update(1.0, now), Some(AlertState::Cleared));
+    }
+}
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+
+/// The position of the diagnostics on screen, see [ScreenDiagnostics::set_anchor](crate::ScreenDiagnostics::set_anchor).
+///
+/// Replaces the position set with [ScreenDiagnosticsPlugin::style](crate::ScreenDiagnosticsPlugin::style).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum DiagnosticsAnchor {
+    /// The top left corner.
+    TopLeft,
+    /// The middle of the top edge.
+    TopCenter,
+    /// The top right corner.
+    TopRight,
+    /// The middle of the left edge.
+    CenterLeft,
+    /// The center of the screen.
+    Center,
+    /// The middle of the right edge.
+    CenterRight,
+    /// The bottom left corner.
+    BottomLeft,
+    /// The middle of the bottom edge.
+    BottomCenter,
+    /// The bottom right corner.
+    #[default]
+    BottomRight,
+}
+
+impl DiagnosticsAnchor {
+    /// All anchors, row by row from the top left to the bottom right.
+    pub const ALL: [Self; 9] = [
+        Self::TopLeft,
+        Self::TopCenter,
+        Self::TopRight,
+        Self::CenterLeft,
+        Self::Center,
+        Self::CenterRight,
+        Self::BottomLeft,
+        Self::BottomCenter,
+        Self::BottomRight,
+    ];
+
+    /// The next anchor in [DiagnosticsAnchor::ALL], wrapping around after the bottom right.
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|a| *a == self)
+            .unwrap_or_default();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub(crate) fn alignment(self) -> (JustifySelf, AlignSelf) {
+        use DiagnosticsAnchor::*;
+        let justify = match self {
+            TopLeft | CenterLeft | BottomLeft => JustifySelf::Start,
+            TopCenter | Center | BottomCenter => JustifySelf::Center,
+            TopRight | CenterRight | BottomRight => JustifySelf::End,
+        };
+        let align = match self {
+            TopLeft | TopCenter | TopRight => AlignSelf::Start,
+            CenterLeft | Center | CenterRight => AlignSelf::Center,
+            BottomLeft | BottomCenter | BottomRight => AlignSelf::End,
+        };
+        (justify, align)
+    }
+
+    /// Position the node at this anchor, `margin` pixels away from the edges of the safe area.
+    ///
+    /// The margin is scaled with the [UiScale] like all other UI values, the safe area is not.
+    pub(crate) fn apply(self, node: &mut Node, margin: Vec2, safe_area: &SafeArea, scale: f32) {
+        let (justify, align) = self.alignment();
+        let px = |margin: f32, inset: f32| Val::Px(margin + inset / scale);
+        node.position_type = PositionType::Absolute;
+        node.left = Val::Auto;
+        node.right = Val::Auto;
+        node.top = Val::Auto;
+        node.bottom = Val::Auto;
+        node.justify_self = justify;
+        node.align_self = align;
+        node.margin = UiRect {
+            left: px(margin.x, safe_area.left),
+            right: px(margin.x, safe_area.right),
+            top: px(margin.y, safe_area.top),
+            bottom: px(margin.y, safe_area.bottom),
+        };
+    }
+}
+
+/// The insets of the screen edges which are not safe to draw on, like notches or rounded display corners.
+///
+/// In logical pixels, these are not scaled with the [UiScale]. See [ScreenDiagnostics::set_safe_area](crate::ScreenDiagnostics::set_safe_area).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+pub struct SafeArea {
+    /// The inset of the top edge.
+    pub top: f32,
+    /// The inset of the right edge.
+    pub right: f32,
+    /// The inset of the bottom edge.
+    pub bottom: f32,
+    /// The inset of the left edge.
+    pub left: f32,
+}
@@ -1,13 +1,15 @@
+use std::fmt::Write;
+
 use bevy::{
     diagnostic::{EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
     prelude::*,
 };
 
-use crate::{Aggregate, ScreenDiagnostics};
+use crate::{Aggregate, ScreenDiagnostics, Unit};
 
 /// Plugin which adds the bevy [`FrameTimeDiagnosticsPlugin`] and adds its diagnostics to [DiagnosticsText]
 ///
-/// Example: ``16.6 ms/frame 60 fps``
+/// Example: ``60 fps 16.6 ms/frame``
 pub struct ScreenFrameDiagnosticsPlugin;
 
 impl Plugin for ScreenFrameDiagnosticsPlugin {
@@ -23,7 +25,7 @@ fn setup_frame_diagnostics(mut diags: ResMut<ScreenDiagnostics>) {
     diags
         .add("fps".to_string(), FrameTimeDiagnosticsPlugin::FPS)
         .aggregate(Aggregate::Value)
-        .format(|v| format!("{v:.0}"));
+        .format_into(|v, s: &mut String| write!(s, "{v:.0}"));
 
     diags
         .add(
@@ -31,7 +33,7 @@ fn setup_frame_diagnostics(mut diags: ResMut<ScreenDiagnostics>) {
             FrameTimeDiagnosticsPlugin::FRAME_TIME,
         )
         .aggregate(Aggregate::MovingAverage(5))
-        .format(|v| format!("{v:.2}"));
+        .format_into(|v, s: &mut String| write!(s, "{v:.2}"));
 }
 
 /// Plugin which adds the bevy [`EntityCountDiagnosticsPlugin`] and adds its diagnostics to [DiagnosticsText]
@@ -55,17 +57,17 @@ fn setup_entity_diagnostics(mut diags: ResMut<ScreenDiagnostics>) {
             EntityCountDiagnosticsPlugin::ENTITY_COUNT,
         )
         .aggregate(Aggregate::Value)
-        .format(|v| format!("{v:.0}"));
+        .unit(Unit::Count);
 }
 #[cfg(feature = "sysinfo_plugin")]
 pub(crate) mod sysinfo_plugin {
     use bevy::{diagnostic::SystemInformationDiagnosticsPlugin, prelude::*};
 
-    use crate::{Aggregate, ScreenDiagnostics};
+    use crate::{Aggregate, ScreenDiagnostics, Unit};
     /// Plugin which adds the bevy [`SystemInformationDiagnosticsPlugin`] and adds its diagnostics to [DiagnosticsText].
     /// "Total" is the value of the entire machine.
     ///
-    /// Example: ``09.8% Memory Total 01.1% Memory 12.5% CPU Total 01.5% CPU``
+    /// Example: ``1.50% CPU 312MiB Memory 12.5% CPU Total 9.80% Memory Total``
     pub struct ScreenSystemInformationDiagnosticsPlugin;
 
     impl Plugin for ScreenSystemInformationDiagnosticsPlugin {
@@ -84,27 +86,27 @@ pub(crate) mod sysinfo_plugin {
                 SystemInformationDiagnosticsPlugin::PROCESS_CPU_USAGE,
             )
             .aggregate(Aggregate::Value)
-            .format(|v| format!("{v:0>4.1}%"));
+            .unit(Unit::Percent);
         diags
             .add(
                 "Memory".to_string(),
                 SystemInformationDiagnosticsPlugin::PROCESS_MEM_USAGE,
             )
-            .aggregate(Aggregate::Value)
-            .format(|v| format!("{v:0>4.1}%"));
+            // in GiB, displayed as bytes by its suffix
+            .aggregate(Aggregate::Value);
         diags
             .add(
                 "CPU Total".to_string(),
                 SystemInformationDiagnosticsPlugin::SYSTEM_CPU_USAGE,
             )
             .aggregate(Aggregate::Value)
-            .format(|v| format!("{v:0>4.1}%"));
+            .unit(Unit::Percent);
         diags
             .add(
                 "Memory Total".to_string(),
-                SystemInformationDiagnosticsPlugin::SYSTEM_CPU_USAGE,
+                SystemInformationDiagnosticsPlugin::SYSTEM_MEM_USAGE,
             )
             .aggregate(Aggregate::Value)
-            .format(|v| format!("{v:0>4.1}%"));
+            .unit(Unit::Percent);
     }
 }
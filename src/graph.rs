@@ -0,0 +1,361 @@
+use bevy::{
+    diagnostic::{
+        DEFAULT_MAX_HISTORY_LENGTH, Diagnostic, DiagnosticsStore, FrameTimeDiagnosticsPlugin,
+    },
+    ecs::system::SystemParam,
+    prelude::*,
+    render::view::RenderLayers,
+};
+
+/// A small bar graph of the recent history of a diagnostic, displayed next to its value.
+///
+/// Enable it with [DiagnosticsTextBuilder::graph](crate::DiagnosticsTextBuilder::graph).
+#[derive(Clone, Debug, Reflect)]
+pub struct Sparkline {
+    /// The amount of history samples shown, one bar per sample. Default: 30
+    ///
+    /// Limited by the history length of the [Diagnostic].
+    pub samples: usize,
+    /// The size of the graph in pixels. Default: 60x16
+    pub size: Vec2,
+    /// The color of the bars. Uses the diagnostic value color when `None`. Default: `None`
+    pub color: Option<Color>,
+}
+
+impl Default for Sparkline {
+    fn default() -> Self {
+        Self {
+            samples: 30,
+            size: Vec2::new(60.0, 16.0),
+            color: None,
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+struct SparklineBar;
+
+impl Sparkline {
+    pub(crate) fn spawn(
+        &self,
+        parent: &mut ChildSpawnerCommands,
+        layer: &RenderLayers,
+        color: Color,
+    ) -> Entity {
+        parent
+            .spawn((
+                Node {
+                    width: Val::Px(self.size.x),
+                    height: Val::Px(self.size.y),
+                    margin: UiRect::horizontal(Val::Px(4.0)),
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::FlexEnd,
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                layer.clone(),
+            ))
+            .with_children(|graph| {
+                for _ in 0..self.samples {
+                    graph.spawn((
+                        Node {
+                            width: Val::Percent(100.0 / self.samples as f32),
+                            height: Val::Percent(0.0),
+                            ..default()
+                        },
+                        BackgroundColor(self.color.unwrap_or(color)),
+                        layer.clone(),
+                        SparklineBar,
+                    ));
+                }
+            })
+            .id()
+    }
+
+    /// Scale the bars to the latest history of the diagnostic, newest on the right.
+    pub(crate) fn update(
+        &self,
+        diagnostic: &Diagnostic,
+        graph: Entity,
+        bars: &mut SparklineBars,
+        color: Color,
+    ) {
+        let Ok(children) = bars.graphs.get(graph) else {
+            return;
+        };
+        let max = history(diagnostic, children.len())
+            .filter(|v| v.is_finite())
+            .fold(0.0, f64::max);
+        let heights =
+            history(diagnostic, children.len()).map(|v| match max > 0.0 && v.is_finite() {
+                true => (v / max * 100.0).max(0.0) as f32,
+                false => 0.0,
+            });
+
+        let color = self.color.unwrap_or(color);
+        for (bar, height) in children.iter().zip(heights) {
+            if let Ok((mut node, mut background)) = bars.bars.get_mut(bar) {
+                set_bar(&mut node, &mut background, height, color);
+            }
+        }
+    }
+}
+
+/// The bars of all [Sparkline]s.
+#[derive(SystemParam)]
+pub(crate) struct SparklineBars<'w, 's> {
+    graphs: Query<'w, 's, &'static Children>,
+    bars: Query<'w, 's, (&'static mut Node, &'static mut BackgroundColor), With<SparklineBar>>,
+}
+
+/// Only touch the bar when it changed, so unchanged bars are not laid out again.
+fn set_bar(node: &mut Mut<Node>, background: &mut Mut<BackgroundColor>, height: f32, color: Color) {
+    if node.height != Val::Percent(height) {
+        node.height = Val::Percent(height);
+    }
+    background.set_if_neq(BackgroundColor(color));
+}
+
+/// The latest `count` values of the diagnostic, oldest first.
+///
+/// Padded at the front with `NaN` when there is less history than `count`.
+fn history(diagnostic: &Diagnostic, count: usize) -> impl Iterator<Item = f64> + '_ {
+    let skip = diagnostic.history_len().saturating_sub(count);
+    let empty = count.saturating_sub(diagnostic.history_len());
+    std::iter::repeat_n(f64::NAN, empty).chain(diagnostic.values().skip(skip).copied())
+}
+
+/// A frame time budget drawn as a horizontal line by the [ScreenFrameGraphPlugin].
+#[derive(Clone, Copy, Debug, Reflect)]
+pub struct FrameBudget {
+    /// The frame time in milliseconds.
+    pub frame_time: f64,
+    /// The color of the line and of the bars exceeding this budget.
+    pub color: Color,
+}
+
+/// Plugin which adds the bevy [`FrameTimeDiagnosticsPlugin`] and draws a scrolling bar chart of the frame times.
+///
+/// Each bar is colored by the highest [FrameBudget] it exceeds.
+///
+/// ```rust
+///# use bevy::prelude::*;
+///# use bevy_screen_diagnostics::{FrameBudget, ScreenFrameGraphPlugin};
+///
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .add_plugins(ScreenFrameGraphPlugin {
+///         budgets: vec![FrameBudget {
+///             frame_time: 1000.0 / 144.0,
+///             color: Color::srgb(1.0, 0.0, 0.0),
+///         }],
+///         ..default()
+///     });
+/// ```
+pub struct ScreenFrameGraphPlugin {
+    /// The Style used to position the graph.
+    ///
+    /// By default this is in the top right corner of the window:
+    /// ```rust
+    ///# use bevy::prelude::*;
+    ///# use bevy_screen_diagnostics::ScreenFrameGraphPlugin;
+    ///
+    ///# fn main() {
+    ///#     App::new()
+    ///#         .add_plugins(DefaultPlugins)
+    ///#         .add_plugins(ScreenFrameGraphPlugin {
+    ///#             style:
+    /// Node {
+    ///     position_type: PositionType::Absolute,
+    ///     top: Val::Px(5.0),
+    ///     right: Val::Px(15.0),
+    ///     ..default()
+    /// },
+    ///#        ..default()
+    ///#    });
+    ///# }
+    /// ```
+    pub style: Node,
+    /// The size of the graph in pixels. Default: 240x80
+    pub size: Vec2,
+    /// The amount of frames shown, one bar per frame. Default: 120
+    ///
+    /// Limited by the history length of the [FrameTimeDiagnosticsPlugin] frame time.
+    /// When this plugin adds the [FrameTimeDiagnosticsPlugin], the history is long enough.
+    /// When it was added before, like by [ScreenFrameDiagnosticsPlugin](crate::ScreenFrameDiagnosticsPlugin),
+    /// its default history of 120 frames leaves the older bars empty.
+    pub samples: usize,
+    /// The frame time in milliseconds at the top of the graph. Longer frames are cut off. Default: 50.0
+    pub max_frame_time: f64,
+    /// The budget lines, in ascending order of frame time. Default: 16.6ms (60fps) and 33.3ms (30fps)
+    pub budgets: Vec<FrameBudget>,
+    /// The color of bars within all budgets.
+    pub color: Color,
+    /// The background color of the graph.
+    pub background: Color,
+    /// The render layer for the UI
+    pub render_layer: RenderLayers,
+    /// The camera displaying the graph, like [UiTargetCamera]. Default: `None`, the default UI camera.
+    ///
+    /// See [ScreenDiagnosticsPlugin::target_camera](crate::ScreenDiagnosticsPlugin::target_camera).
+    pub target_camera: Option<Entity>,
+}
+
+impl Default for ScreenFrameGraphPlugin {
+    fn default() -> Self {
+        Self {
+            style: Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(5.0),
+                right: Val::Px(15.0),
+                ..default()
+            },
+            size: Vec2::new(240.0, 80.0),
+            samples: 120,
+            max_frame_time: 50.0,
+            budgets: vec![
+                FrameBudget {
+                    frame_time: 1000.0 / 60.0,
+                    color: Color::srgb(1.0, 0.8, 0.0),
+                },
+                FrameBudget {
+                    frame_time: 1000.0 / 30.0,
+                    color: Color::srgb(1.0, 0.2, 0.2),
+                },
+            ],
+            color: Color::srgb(0.2, 0.8, 0.3),
+            background: Color::srgba(0.0, 0.0, 0.0, 0.5),
+            render_layer: RenderLayers::default(),
+            target_camera: None,
+        }
+    }
+}
+
+#[derive(Resource, Reflect)]
+struct FrameGraphSettings {
+    style: Node,
+    size: Vec2,
+    samples: usize,
+    max_frame_time: f64,
+    budgets: Vec<FrameBudget>,
+    color: Color,
+    background: Color,
+    render_layer: RenderLayers,
+    target_camera: Option<Entity>,
+}
+
+#[derive(Component, Reflect)]
+struct FrameGraphMarker;
+
+#[derive(Component, Reflect)]
+struct FrameGraphBar;
+
+impl Plugin for ScreenFrameGraphPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::new(
+                self.samples.max(DEFAULT_MAX_HISTORY_LENGTH),
+            ));
+        }
+        app.insert_resource(FrameGraphSettings {
+            style: self.style.clone(),
+            size: self.size,
+            samples: self.samples,
+            max_frame_time: self.max_frame_time,
+            budgets: self.budgets.clone(),
+            color: self.color,
+            background: self.background,
+            render_layer: self.render_layer.clone(),
+            target_camera: self.target_camera,
+        })
+        .add_systems(Startup, spawn_frame_graph)
+        .add_systems(Update, update_frame_graph);
+    }
+}
+
+impl FrameGraphSettings {
+    fn height_percent(&self, frame_time: f64) -> f32 {
+        (frame_time / self.max_frame_time * 100.0).clamp(0.0, 100.0) as f32
+    }
+
+    fn bar_color(&self, frame_time: f64) -> Color {
+        self.budgets
+            .iter()
+            .rev()
+            .find(|budget| frame_time > budget.frame_time)
+            .map_or(self.color, |budget| budget.color)
+    }
+}
+
+fn spawn_frame_graph(mut commands: Commands, settings: Res<FrameGraphSettings>) {
+    let layer = &settings.render_layer;
+    let mut frame_graph = commands.spawn((
+        Node {
+            width: Val::Px(settings.size.x),
+            height: Val::Px(settings.size.y),
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::FlexEnd,
+            overflow: Overflow::clip(),
+            ..settings.style.clone()
+        },
+        BackgroundColor(settings.background),
+        layer.clone(),
+        FrameGraphMarker,
+    ));
+    frame_graph.with_children(|graph| {
+        for _ in 0..settings.samples {
+            graph.spawn((
+                Node {
+                    width: Val::Percent(100.0 / settings.samples as f32),
+                    height: Val::Percent(0.0),
+                    ..default()
+                },
+                BackgroundColor(settings.color),
+                layer.clone(),
+                FrameGraphBar,
+            ));
+        }
+        for budget in &settings.budgets {
+            graph.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Percent(settings.height_percent(budget.frame_time)),
+                    width: Val::Percent(100.0),
+                    height: Val::Px(1.0),
+                    ..default()
+                },
+                BackgroundColor(budget.color),
+                layer.clone(),
+            ));
+        }
+    });
+    if let Some(camera) = settings.target_camera {
+        frame_graph.insert(UiTargetCamera(camera));
+    }
+}
+
+fn update_frame_graph(
+    settings: Res<FrameGraphSettings>,
+    diagnostics: Res<DiagnosticsStore>,
+    graph: Single<&Children, With<FrameGraphMarker>>,
+    mut bars: Query<(&mut Node, &mut BackgroundColor), With<FrameGraphBar>>,
+) {
+    let Some(frame_time) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME) else {
+        return;
+    };
+    let frame_times = history(frame_time, settings.samples);
+    for (bar, frame_time) in graph.iter().zip(frame_times) {
+        let Ok((mut node, mut background)) = bars.get_mut(bar) else {
+            continue;
+        };
+        match frame_time.is_finite() {
+            true => set_bar(
+                &mut node,
+                &mut background,
+                settings.height_percent(frame_time),
+                settings.bar_color(frame_time),
+            ),
+            false => set_bar(&mut node, &mut background, 0.0, settings.color),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::{fmt::Write, marker::PhantomData};
+
+use bevy::prelude::*;
+
+use crate::{DiagnosticsText, ScreenDiagnostics, ScreenDiagnosticsTheme};
+
+/// What clicking the name of a diagnostic does, see [ScreenDiagnosticsPlugin::click](crate::ScreenDiagnosticsPlugin::click).
+///
+/// Hovering the name shows its [DiagnosticPath](bevy::diagnostic::DiagnosticPath) and [Aggregate](crate::Aggregate).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum DiagnosticsClick {
+    /// Show or hide the value, see [DiagnosticsTextBuilder::toggle_value](crate::DiagnosticsTextBuilder::toggle_value).
+    #[default]
+    ToggleValue,
+    /// Show or hide a history graph next to the value, see [DiagnosticsTextBuilder::toggle_graph](crate::DiagnosticsTextBuilder::toggle_graph).
+    ToggleGraph,
+}
+
+/// The text showing the path and aggregate of the hovered diagnostic, next to the panel.
+#[derive(Component)]
+#[require(Text, TextLayout::new_with_no_wrap())]
+pub(crate) struct DiagnosticsTooltip<M> {
+    /// The name which is described, only it hides the tooltip when it is no longer hovered.
+    hovered: Option<Entity>,
+    panel: PhantomData<fn() -> M>,
+}
+
+impl<M> Default for DiagnosticsTooltip<M> {
+    fn default() -> Self {
+        Self {
+            hovered: None,
+            panel: PhantomData,
+        }
+    }
+}
+
+type TooltipStyle<'a, M> = (
+    &'a mut DiagnosticsTooltip<M>,
+    &'a mut Node,
+    &'a mut Text,
+    &'a mut TextFont,
+    &'a mut TextColor,
+    &'a mut BackgroundColor,
+);
+
+pub(crate) fn handle_interactions<M: Send + Sync + 'static>(
+    mut diags: ResMut<ScreenDiagnostics<M>>,
+    theme: Res<ScreenDiagnosticsTheme>,
+    interactions: Query<(Entity, &Interaction), Changed<Interaction>>,
+    tooltip: Single<TooltipStyle<M>>,
+) {
+    let Some(click) = diags.click else {
+        return;
+    };
+    let (mut state, mut node, mut tooltip, mut font, mut color, mut background) =
+        tooltip.into_inner();
+    // the hovered name is despawned when the layout is rebuilt, like after a click
+    if let Some(hovered) = state.hovered
+        && !diags
+            .diagnostics
+            .iter()
+            .any(|text| text.entities.is_some_and(|e| e.name == hovered))
+    {
+        node.display = Display::None;
+        state.hovered = None;
+    }
+    for (entity, interaction) in &interactions {
+        // the interactions of all panels are changed here, only handle the names of this one
+        let Some(text) = diags
+            .diagnostics
+            .iter()
+            .find(|text| text.entities.is_some_and(|e| e.name == entity))
+        else {
+            continue;
+        };
+        match interaction {
+            Interaction::Pressed => {
+                let name = text.name.clone();
+                let entry = diags.modify(name);
+                match click {
+                    DiagnosticsClick::ToggleValue => entry.toggle_value(),
+                    DiagnosticsClick::ToggleGraph => entry.toggle_graph(),
+                };
+            }
+            Interaction::Hovered => {
+                tooltip.0.clear();
+                let _ = write_details(text, &mut tooltip.0);
+
+                let panel = diags.background.as_ref().unwrap_or(&theme.background);
+                font.font = diags.font.clone();
+                font.font_size = diags.font_size.unwrap_or(theme.font_size);
+                color.0 = theme.name_color;
+                background.0 = panel.color;
+                node.padding = panel.padding;
+
+                // towards the middle of the screen, so it isn't cut off
+                let (justify, align) = diags.anchor.unwrap_or_default().alignment();
+                let (left, right) = match justify {
+                    JustifySelf::Start => (Val::Px(0.0), Val::Auto),
+                    _ => (Val::Auto, Val::Px(0.0)),
+                };
+                let (top, bottom) = match align {
+                    AlignSelf::Start => (Val::Percent(100.0), Val::Auto),
+                    _ => (Val::Auto, Val::Percent(100.0)),
+                };
+                node.left = left;
+                node.right = right;
+                node.top = top;
+                node.bottom = bottom;
+                node.display = Display::Flex;
+                state.hovered = Some(entity);
+            }
+            // the cursor can move onto the next name before this one is handled
+            Interaction::None if state.hovered == Some(entity) => {
+                node.display = Display::None;
+                state.hovered = None;
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+/// Write the path and aggregate of the diagnostic, like ``render/fps MovingAverage(5)``
+fn write_details(text: &DiagnosticsText, s: &mut String) -> std::fmt::Result {
+    match text.custom_agg {
+        Some(_) => write!(s, "{} custom", text.path),
+        None => write!(s, "{} {:?}", text.path, text.agg),
+    }
+}
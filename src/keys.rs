@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::ScreenDiagnostics;
+
+/// Key bindings to control the diagnostics at runtime, see [ScreenDiagnosticsPlugin::keys](crate::ScreenDiagnosticsPlugin::keys).
+///
+/// Actions without a key are disabled. By default no keys are bound.
+///
+/// ```rust
+///# use bevy::prelude::*;
+///# use bevy_screen_diagnostics::{DiagnosticsKeys, ScreenDiagnosticsPlugin};
+///
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .add_plugins(ScreenDiagnosticsPlugin {
+///         keys: DiagnosticsKeys {
+///             toggle: Some(KeyCode::F3),
+///             ..default()
+///         },
+///         ..default()
+///     });
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+pub struct DiagnosticsKeys {
+    /// Show or hide the whole panel, see [ScreenDiagnostics::toggle_visible].
+    pub toggle: Option<KeyCode>,
+    /// Switch to the next preset, see [ScreenDiagnostics::cycle_preset].
+    pub cycle_preset: Option<KeyCode>,
+    /// Show or hide the names of all diagnostics, see [ScreenDiagnostics::toggle_names].
+    pub toggle_names: Option<KeyCode>,
+    /// Move the panel to the next anchor, see [ScreenDiagnostics::cycle_anchor].
+    pub cycle_anchor: Option<KeyCode>,
+}
+
+impl DiagnosticsKeys {
+    /// F9 to toggle the panel, F10 to cycle presets, F11 to toggle names and F12 to cycle the anchor.
+    pub fn function_keys() -> Self {
+        Self {
+            toggle: Some(KeyCode::F9),
+            cycle_preset: Some(KeyCode::F10),
+            toggle_names: Some(KeyCode::F11),
+            cycle_anchor: Some(KeyCode::F12),
+        }
+    }
+}
+
+pub(crate) fn handle_keys<M: Send + Sync + 'static>(
+    input: Res<ButtonInput<KeyCode>>,
+    mut diags: ResMut<ScreenDiagnostics<M>>,
+) {
+    let keys = diags.keys;
+    let pressed = |key: Option<KeyCode>| key.is_some_and(|key| input.just_pressed(key));
+    if pressed(keys.toggle) {
+        diags.toggle_visible();
+    }
+    if pressed(keys.cycle_preset) {
+        diags.cycle_preset();
+    }
+    if pressed(keys.toggle_names) {
+        diags.toggle_names();
+    }
+    if pressed(keys.cycle_anchor) {
+        diags.cycle_anchor();
+    }
+}
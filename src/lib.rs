@@ -2,27 +2,64 @@
 #![warn(missing_docs)]
 #![warn(rustdoc::missing_doc_code_examples)]
 
-use std::{collections::BTreeMap, time::Duration};
+use std::{
+    any,
+    cmp::Reverse,
+    fmt::{self, Write},
+    iter,
+    marker::PhantomData,
+    ops::RangeInclusive,
+    sync::Arc,
+    time::Duration,
+};
 
-use bevy::color::palettes::css;
 use bevy::{
-    diagnostic::{DiagnosticPath, DiagnosticsStore},
+    diagnostic::{Diagnostic, DiagnosticPath, DiagnosticsStore},
+    ecs::system::SystemParam,
     prelude::*,
+    reflect::utility::GenericTypePathCell,
     render::view::RenderLayers,
-    text::LineBreak,
+    text::LineHeight,
     time::common_conditions::on_timer,
 };
 
+mod aggregate;
+mod alert;
+mod anchor;
 mod extras;
+mod graph;
+mod interaction;
+mod keys;
+mod theme;
+mod unit;
+mod world;
 
+use self::aggregate::AggregateState;
+pub use self::aggregate::{Aggregate, DiagnosticAggregator};
+use self::alert::{Alert, AlertWriter};
+pub use self::alert::{AlertBound, AlertState, AlertThreshold, DiagnosticAlert};
+pub use self::anchor::{DiagnosticsAnchor, SafeArea};
 #[cfg(feature = "sysinfo_plugin")]
 pub use self::extras::sysinfo_plugin::ScreenSystemInformationDiagnosticsPlugin;
 pub use self::extras::{ScreenEntityDiagnosticsPlugin, ScreenFrameDiagnosticsPlugin};
+use self::graph::SparklineBars;
+pub use self::graph::{FrameBudget, ScreenFrameGraphPlugin, Sparkline};
+pub use self::interaction::DiagnosticsClick;
+use self::interaction::{DiagnosticsTooltip, handle_interactions};
+pub use self::keys::DiagnosticsKeys;
+use self::keys::handle_keys;
+pub use self::theme::{DiagnosticsBackground, ScreenDiagnosticsTheme};
+pub use self::unit::Unit;
+pub use self::world::EntityDiagnostics;
+use self::world::EntityDiagnosticsPlugin;
 
 const TIMESTEP_10_PER_SECOND: f64 = 1.0 / 10.0;
 
 /// Plugin for displaying Diagnostics on screen.
-pub struct ScreenDiagnosticsPlugin {
+///
+/// Add it multiple times with different marker types to display multiple independent panels,
+/// see [ScreenDiagnosticsPlugin::with_panel].
+pub struct ScreenDiagnosticsPlugin<M = DefaultDiagnosticsPanel> {
     /// The rate at which the diagnostics on screen are updated. Default: 1.0/10.0 (10 times per second).
     pub timestep: f64,
     /// The Style used to position the Text.
@@ -49,13 +86,107 @@ pub struct ScreenDiagnosticsPlugin {
     ///# }
     /// ```
     pub style: Node,
+    /// Position the diagnostics at one of the edges or corners of the screen, instead of with the `style`. Default: `None`
+    ///
+    /// ```rust
+    ///# use bevy::prelude::*;
+    ///# use bevy_screen_diagnostics::{DiagnosticsAnchor, ScreenDiagnosticsPlugin};
+    ///
+    /// App::new()
+    ///     .add_plugins(DefaultPlugins)
+    ///     .add_plugins(ScreenDiagnosticsPlugin {
+    ///         anchor: Some(DiagnosticsAnchor::TopLeft),
+    ///         margin: Vec2::splat(10.0),
+    ///         ..default()
+    ///     });
+    /// ```
+    pub anchor: Option<DiagnosticsAnchor>,
+    /// The horizontal and vertical distance in pixels from the edges of the screen when using an `anchor`. Default: 15x5
+    pub margin: Vec2,
     /// The font used for the text. By default [FiraCodeBold](https://github.com/tonsky/FiraCode) is used.
     pub font: Option<&'static str>,
+    /// The line height of the text. Default: [LineHeight::RelativeToFont] of 1.2
+    pub line_height: LineHeight,
     /// The render layer for the UI
     pub render_layer: RenderLayers,
+    /// The camera displaying the diagnostics, like [UiTargetCamera]. Default: `None`, the default UI camera.
+    ///
+    /// Use it to display the diagnostics in a specific window or viewport.
+    /// The camera has to be spawned before the plugin is added,
+    /// or set at runtime with [ScreenDiagnostics::set_target_camera].
+    ///
+    /// ```rust
+    ///# use bevy::prelude::*;
+    ///# use bevy_screen_diagnostics::ScreenDiagnosticsPlugin;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins(DefaultPlugins);
+    /// let camera = app.world_mut().spawn(Camera2d).id();
+    /// app.add_plugins(ScreenDiagnosticsPlugin {
+    ///     target_camera: Some(camera),
+    ///     ..default()
+    /// });
+    /// ```
+    pub target_camera: Option<Entity>,
+    /// How the diagnostics are arranged. Default: [DiagnosticsLayout::Inline]
+    pub layout: DiagnosticsLayout,
+    /// The colors, font size and background of the diagnostics. Default: [ScreenDiagnosticsTheme::default]
+    ///
+    /// Inserted as a resource by the first added panel, which can be changed at runtime.
+    /// It is shared by all panels, the theme of later panels is not used.
+    /// Set the `font_size` and `background` of a panel to override the theme,
+    /// and the colors of single diagnostics with [DiagnosticsTextBuilder::diagnostic_color].
+    ///
+    /// The labels of [EntityDiagnostics] use the timestep, font, line height and render layer of the first added panel.
+    pub theme: ScreenDiagnosticsTheme,
+    /// The font size of this panel, instead of the one of the [ScreenDiagnosticsTheme]. Default: `None`
+    ///
+    /// Can be changed at runtime with [ScreenDiagnostics::set_font_size].
+    pub font_size: Option<f32>,
+    /// The background of this panel, instead of the one of the [ScreenDiagnosticsTheme]. Default: `None`
+    ///
+    /// Can be changed at runtime with [ScreenDiagnostics::set_background].
+    pub background: Option<DiagnosticsBackground>,
+    /// The keys to toggle and cycle the diagnostics at runtime. Default: none
+    pub keys: DiagnosticsKeys,
+    /// What clicking the name of a diagnostic does. Default: `None`, the diagnostics can't be clicked or hovered.
+    ///
+    /// ```rust
+    ///# use bevy::prelude::*;
+    ///# use bevy_screen_diagnostics::{DiagnosticsClick, ScreenDiagnosticsPlugin};
+    ///
+    /// App::new()
+    ///     .add_plugins(DefaultPlugins)
+    ///     .add_plugins(ScreenDiagnosticsPlugin {
+    ///         click: Some(DiagnosticsClick::ToggleGraph),
+    ///         ..default()
+    ///     });
+    /// ```
+    pub click: Option<DiagnosticsClick>,
+    /// The marker type of the panel, see [ScreenDiagnosticsPlugin::with_panel].
+    pub panel: PhantomData<fn() -> M>,
 }
 
-const DEFAULT_COLORS: (Srgba, Srgba) = (css::RED, css::WHITE);
+/// The marker type of the panel used by [ScreenDiagnosticsPlugin] and [ScreenDiagnostics] when none is given.
+#[derive(Reflect)]
+pub struct DefaultDiagnosticsPanel;
+
+/// How the diagnostics are arranged on screen, see [ScreenDiagnostics::set_layout].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+pub enum DiagnosticsLayout {
+    /// All diagnostics next to each other, wrapping when they don't fit.
+    #[default]
+    Inline,
+    /// One row per diagnostic, with the values right-aligned in a column and the names next to them.
+    ///
+    /// The value column is at least `value_width` pixels wide,
+    /// so values don't move around while they update.
+    /// The columns line up across all groups, with the group headers spanning the whole row.
+    Table {
+        /// The minimum width of the value column in pixels.
+        value_width: f32,
+    },
+}
 
 impl Default for ScreenDiagnosticsPlugin {
     fn default() -> Self {
@@ -68,105 +199,354 @@ impl Default for ScreenDiagnosticsPlugin {
                 right: Val::Px(15.0),
                 ..default()
             },
+            anchor: None,
+            margin: Vec2::new(15.0, 5.0),
             font: None,
+            line_height: LineHeight::default(),
             render_layer: RenderLayers::default(),
+            target_camera: None,
+            layout: DiagnosticsLayout::Inline,
+            theme: ScreenDiagnosticsTheme::default(),
+            font_size: None,
+            background: None,
+            keys: DiagnosticsKeys::default(),
+            click: None,
+            panel: PhantomData,
         }
     }
 }
 
-#[derive(Resource)]
-struct FontOption(Option<&'static str>);
-
-#[derive(Resource, Reflect)]
-struct DiagnosticsStyle(Node);
-
-#[derive(Resource, Deref, Reflect)]
-struct DiagnosticsLayer(RenderLayers);
+impl<M> ScreenDiagnosticsPlugin<M> {
+    /// Display the diagnostics in the panel with the marker type `N`, instead of the [DefaultDiagnosticsPanel].
+    ///
+    /// Each panel has its own [ScreenDiagnostics] resource, accessed with the same marker type.
+    ///
+    /// ```rust
+    ///# use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+    ///# use bevy_screen_diagnostics::{DiagnosticsAnchor, ScreenDiagnostics, ScreenDiagnosticsPlugin};
+    ///
+    /// struct FramePanel;
+    ///
+    /// App::new()
+    ///     .add_plugins(DefaultPlugins)
+    ///     .add_plugins(ScreenDiagnosticsPlugin::default())
+    ///     .add_plugins(
+    ///         ScreenDiagnosticsPlugin {
+    ///             anchor: Some(DiagnosticsAnchor::TopLeft),
+    ///             ..default()
+    ///         }
+    ///         .with_panel::<FramePanel>(),
+    ///     )
+    ///     .add_systems(Startup, setup);
+    ///
+    /// fn setup(mut frame_panel: ResMut<ScreenDiagnostics<FramePanel>>) {
+    ///     frame_panel.add("fps", FrameTimeDiagnosticsPlugin::FPS);
+    /// }
+    /// ```
+    pub fn with_panel<N>(self) -> ScreenDiagnosticsPlugin<N> {
+        ScreenDiagnosticsPlugin {
+            timestep: self.timestep,
+            style: self.style,
+            anchor: self.anchor,
+            margin: self.margin,
+            font: self.font,
+            line_height: self.line_height,
+            render_layer: self.render_layer,
+            target_camera: self.target_camera,
+            layout: self.layout,
+            theme: self.theme,
+            font_size: self.font_size,
+            background: self.background,
+            keys: self.keys,
+            click: self.click,
+            panel: PhantomData,
+        }
+    }
+}
 
-impl Plugin for ScreenDiagnosticsPlugin {
+impl<M: Send + Sync + 'static> Plugin for ScreenDiagnosticsPlugin<M> {
     fn build(&self, app: &mut App) {
-        app.init_resource::<ScreenDiagnostics>()
-            .insert_resource(FontOption(self.font))
-            .init_resource::<ScreenDiagnosticsFont>()
-            .insert_resource(DiagnosticsStyle(self.style.clone()))
-            .insert_resource(DiagnosticsLayer(self.render_layer.clone()))
-            .add_systems(Startup, spawn_ui)
-            .add_systems(
-                Update,
-                (update_onscreen_diags_layout, update_diags)
-                    .chain()
-                    .run_if(on_timer(Duration::from_secs_f64(self.timestep))),
-            );
+        let font = load_font(app.world(), self.font);
+        if !app.world().contains_resource::<ScreenDiagnosticsTheme>() {
+            app.insert_resource(self.theme.clone());
+        }
+        if !app.is_plugin_added::<EntityDiagnosticsPlugin>() {
+            app.add_plugins(EntityDiagnosticsPlugin {
+                timestep: self.timestep,
+                font: font.clone(),
+                line_height: self.line_height,
+                render_layer: self.render_layer.clone(),
+            });
+        }
+        app.insert_resource(ScreenDiagnostics::<M> {
+            style: self.style.clone(),
+            render_layer: self.render_layer.clone(),
+            target_camera: self.target_camera,
+            layout: self.layout,
+            anchor: self.anchor,
+            margin: self.margin,
+            font,
+            line_height: self.line_height,
+            font_size: self.font_size,
+            background: self.background.clone(),
+            keys: self.keys,
+            click: self.click,
+            ..default()
+        })
+        .add_event::<DiagnosticAlert>()
+        .add_systems(Startup, spawn_ui::<M>)
+        .add_systems(
+            Update,
+            (
+                handle_keys::<M>.run_if(resource_exists::<ButtonInput<KeyCode>>),
+                handle_interactions::<M>,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                spawn_ui::<M>,
+                update_panel::<M>,
+                update_onscreen_diags_layout::<M>,
+                update_diags::<M>,
+            )
+                .chain()
+                .run_if(on_timer(Duration::from_secs_f64(self.timestep))),
+        );
     }
 }
 
-#[derive(Resource, Reflect)]
-struct ScreenDiagnosticsFont(Handle<Font>);
-
-impl FromWorld for ScreenDiagnosticsFont {
-    fn from_world(world: &mut World) -> Self {
-        let font = world.get_resource::<FontOption>().unwrap();
-        let assets = world.get_resource::<AssetServer>().unwrap();
-        let font = match font.0 {
-            Some(font) => assets.load(font),
-            #[cfg(not(feature = "builtin-font"))]
-            None => panic!(
-                "No default font supplied, please either set the `builtin-font` \
-                 feature or provide your own font file by setting the `font` field of \
-                 `ScreenDiagnosticsPlugin` to `Some(\"font_asset_path\")`"
-            ),
-            #[cfg(feature = "builtin-font")]
-            None => Default::default(),
-        };
-        Self(font)
+fn load_font(world: &World, font: Option<&'static str>) -> Handle<Font> {
+    let assets = world.get_resource::<AssetServer>().unwrap();
+    match font {
+        Some(font) => assets.load(font),
+        #[cfg(not(feature = "builtin-font"))]
+        None => panic!(
+            "No default font supplied, please either set the `builtin-font` \
+             feature or provide your own font file by setting the `font` field of \
+             `ScreenDiagnosticsPlugin` to `Some(\"font_asset_path\")`"
+        ),
+        #[cfg(feature = "builtin-font")]
+        None => Default::default(),
     }
 }
 
-#[derive(Component, Reflect)]
-#[require(Text)]
-struct DiagnosticsTextMarker;
+#[derive(Component)]
+#[require(Node)]
+struct DiagnosticsTextMarker<M>(PhantomData<fn() -> M>);
 
-/// Aggregaes which can be used for displaying Diagnostics.
-#[derive(Copy, Clone, Default, Debug, Reflect)]
-pub enum Aggregate {
-    /// The latest [Diagnostic::value]
-    #[default]
-    Value,
-    /// The [Diagnostic::average] of all recorded diagnostic measurements.
-    #[allow(dead_code)]
-    Average,
-    /// A moving average over n last diagnostic measurements.
-    ///
-    /// If this is larger than the amount of diagnostic measurement stored for that diagnostic, no update will happen.
-    MovingAverage(usize),
-}
+/// The panel containing the [DiagnosticsTextMarker] root, positioned with [ScreenDiagnosticsPlugin::style].
+#[derive(Component)]
+#[require(Node)]
+struct DiagnosticsPanelMarker<M>(PhantomData<fn() -> M>);
 
 /// Type alias for the fuction used to format a diagnostic value to a string.
 ///
 /// Useful especially for applying some operations to the value before formatting.
 ///
 /// Example: ``|v| format!("{:.2}", v);`` which limits the decimal places to 1.
-pub type FormatFn = fn(f64) -> String;
+///
+/// Can capture state, like a precision chosen at runtime, see [DiagnosticsTextBuilder::format].
+pub type FormatFn = Arc<dyn Fn(f64) -> String + Send + Sync>;
+
+/// Type alias for the function used to write a diagnostic value into a reused string, see [DiagnosticsTextBuilder::format_into].
+///
+/// Example: ``|v, s| write!(s, "{v:.2}")``
+pub type FormatIntoFn = Arc<dyn Fn(f64, &mut String) -> fmt::Result + Send + Sync>;
+
+/// Conversion into a [FormatFn], implemented for [FormatFn] itself and for functions and closures.
+///
+/// Closures calling methods on the value need its type, like ``|v: f64| v.to_string()``.
+pub trait IntoFormatFn {
+    /// Convert into a [FormatFn].
+    fn into_format_fn(self) -> FormatFn;
+}
+
+impl IntoFormatFn for FormatFn {
+    fn into_format_fn(self) -> FormatFn {
+        self
+    }
+}
+
+impl<F> IntoFormatFn for F
+where
+    F: Fn(f64) -> String + Send + Sync + 'static,
+{
+    fn into_format_fn(self) -> FormatFn {
+        Arc::new(self)
+    }
+}
+
+/// Conversion into a [FormatIntoFn], implemented for [FormatIntoFn] itself and for functions and closures.
+///
+/// Closures need the type of the string, like ``|v, s: &mut String| write!(s, "{v:.2}")``.
+pub trait IntoFormatIntoFn {
+    /// Convert into a [FormatIntoFn].
+    fn into_format_into_fn(self) -> FormatIntoFn;
+}
+
+impl IntoFormatIntoFn for FormatIntoFn {
+    fn into_format_into_fn(self) -> FormatIntoFn {
+        self
+    }
+}
+
+impl<F> IntoFormatIntoFn for F
+where
+    F: Fn(f64, &mut String) -> fmt::Result + Send + Sync + 'static,
+{
+    fn into_format_into_fn(self) -> FormatIntoFn {
+        Arc::new(self)
+    }
+}
+
+#[derive(Clone)]
+enum Formatter {
+    String(FormatFn),
+    Into(FormatIntoFn),
+}
 
 /// Resource which maps the name to the [DiagnosticPath], [Aggregate] and [ConvertFn]
+///
+/// There is one for each panel, with the marker type given to [ScreenDiagnosticsPlugin::with_panel].
 #[derive(Resource, Reflect)]
-#[reflect(from_reflect = false)]
-pub struct ScreenDiagnostics {
+#[reflect(from_reflect = false, type_path = false)]
+pub struct ScreenDiagnostics<M = DefaultDiagnosticsPanel> {
+    style: Node,
+    render_layer: RenderLayers,
+    target_camera: Option<Entity>,
     text_alignment: JustifyText,
-    diagnostics: BTreeMap<String, DiagnosticsText>,
+    layout: DiagnosticsLayout,
+    anchor: Option<DiagnosticsAnchor>,
+    margin: Vec2,
+    safe_area: SafeArea,
+    font: Handle<Font>,
+    font_size: Option<f32>,
+    line_height: LineHeight,
+    background: Option<DiagnosticsBackground>,
+    visible: bool,
+    keys: DiagnosticsKeys,
+    click: Option<DiagnosticsClick>,
+    diagnostics: Vec<DiagnosticsText>,
+    groups: Vec<DiagnosticsGroup>,
+    presets: Vec<Vec<String>>,
+    preset: Option<usize>,
     layout_changed: bool,
+    #[reflect(ignore)]
+    panel: PhantomData<fn() -> M>,
+}
+
+// implemented by hand, so the marker types don't have to derive Reflect
+impl<M: 'static> TypePath for ScreenDiagnostics<M> {
+    fn type_path() -> &'static str {
+        static CELL: GenericTypePathCell = GenericTypePathCell::new();
+        CELL.get_or_insert::<Self, _>(|| {
+            format!(
+                "bevy_screen_diagnostics::ScreenDiagnostics<{}>",
+                any::type_name::<M>()
+            )
+        })
+    }
+
+    fn short_type_path() -> &'static str {
+        static CELL: GenericTypePathCell = GenericTypePathCell::new();
+        CELL.get_or_insert::<Self, _>(|| {
+            let marker = any::type_name::<M>();
+            let marker = marker.rsplit("::").next().unwrap_or(marker);
+            format!("ScreenDiagnostics<{marker}>")
+        })
+    }
 }
 
-impl Default for ScreenDiagnostics {
+impl<M> Default for ScreenDiagnostics<M> {
     fn default() -> Self {
         Self {
+            style: Node::default(),
+            render_layer: RenderLayers::default(),
+            target_camera: None,
             text_alignment: JustifyText::Left,
+            layout: DiagnosticsLayout::Inline,
+            anchor: None,
+            margin: Vec2::new(15.0, 5.0),
+            safe_area: SafeArea::default(),
+            font: Handle::default(),
+            font_size: None,
+            line_height: LineHeight::default(),
+            background: None,
+            visible: true,
+            keys: DiagnosticsKeys::default(),
+            click: None,
             diagnostics: Default::default(),
+            groups: Default::default(),
+            presets: Default::default(),
+            preset: None,
             layout_changed: Default::default(),
+            panel: PhantomData,
+        }
+    }
+}
+
+/// A named section of diagnostics with a header, see [ScreenDiagnostics::group].
+#[derive(Reflect)]
+struct DiagnosticsGroup {
+    name: String,
+    color: Option<Color>,
+    collapsed: bool,
+}
+
+impl DiagnosticsGroup {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            color: None,
+            collapsed: false,
+        }
+    }
+
+    fn header(&self) -> String {
+        match self.collapsed {
+            true => format!("[+] {}", self.name),
+            false => format!("[-] {}", self.name),
         }
     }
 }
 
+/// Builder-like interface for a group of diagnostics.
+pub struct DiagnosticsGroupBuilder<'a> {
+    group: &'a mut DiagnosticsGroup,
+    layout_changed: &'a mut bool,
+}
+
+impl DiagnosticsGroupBuilder<'_> {
+    /// Set the text color for the group header, instead of the name color of the [ScreenDiagnosticsTheme]
+    pub fn color(self, color: Color) -> Self {
+        self.group.color = Some(color);
+        *self.layout_changed = true;
+        self
+    }
+
+    /// Collapse the group, only showing its header.
+    pub fn collapse(self) -> Self {
+        self.group.collapsed = true;
+        *self.layout_changed = true;
+        self
+    }
+
+    /// Expand the group, showing its diagnostics below the header.
+    pub fn expand(self) -> Self {
+        self.group.collapsed = false;
+        *self.layout_changed = true;
+        self
+    }
+
+    /// Toggle whether the group is collapsed.
+    pub fn toggle_collapsed(self) -> Self {
+        self.group.collapsed = !self.group.collapsed;
+        *self.layout_changed = true;
+        self
+    }
+}
+
 // ngl, i still haven't fully grasped the various parts of bevy_reflect
 // so i don't know if a placeholder like this as a default is useful
 // hell, i dont even know if deriving Reflect on this at all will be useful...
@@ -175,9 +555,6 @@ const PLACEHOLDER_DIAGNOSTIC_PATH: DiagnosticPath =
 fn placeholder_path() -> DiagnosticPath {
     PLACEHOLDER_DIAGNOSTIC_PATH
 }
-fn placeholder_format() -> FormatFn {
-    |v| format!("{v:.2}")
-}
 
 #[derive(Reflect)]
 struct DiagnosticsText {
@@ -186,163 +563,880 @@ struct DiagnosticsText {
     #[reflect(ignore, default = "placeholder_path")]
     path: DiagnosticPath,
     agg: Aggregate,
-    // might not be useful to have reflect here at all, but i needed this to make it not complain
-    #[reflect(ignore, default = "placeholder_format")]
-    format: FormatFn,
+    #[reflect(ignore)]
+    agg_state: AggregateState,
+    #[reflect(ignore)]
+    custom_agg: Option<Box<dyn DiagnosticAggregator>>,
+    unit: Option<Unit>,
+    #[reflect(ignore)]
+    format: Option<Formatter>,
     show: bool,
     show_name: bool,
-    colors: (Color, Color),
+    show_value: bool,
+    /// The value and name colors, using the [ScreenDiagnosticsTheme] when `None`.
+    colors: (Option<Color>, Option<Color>),
+    value_color: ValueColor,
+    #[reflect(ignore)]
+    alerts: Vec<Alert>,
+    graph: Option<Sparkline>,
+    /// Whether the [Sparkline] is displayed, see [DiagnosticsTextBuilder::toggle_graph].
+    expanded: bool,
+    font: Option<Handle<Font>>,
+    font_size: Option<f32>,
+    line_height: Option<LineHeight>,
+    group: Option<String>,
+    priority: i32,
+    /// The insertion order, rewritten by [ScreenDiagnostics::move_to].
+    seq: usize,
     edit: bool,
     rebuild: bool,
-    index: Option<usize>,
+    entities: Option<DiagnosticsTextEntities>,
+}
+
+/// How the color of a diagnostic value is chosen.
+#[derive(Reflect, Default, Clone)]
+enum ValueColor {
+    /// Always the value color set with [DiagnosticsTextBuilder::diagnostic_color].
+    #[default]
+    Fixed,
+    /// The color of the first bound the value is below, see [DiagnosticsTextBuilder::thresholds].
+    Thresholds(Vec<(f64, Color)>),
+    /// A blend between the colors depending on where the value is in the range, see [DiagnosticsTextBuilder::gradient].
+    Gradient {
+        min: f64,
+        max: f64,
+        colors: Vec<Color>,
+    },
+    /// Like [ValueColor::Thresholds], with the colors of the theme palette, see [DiagnosticsTextBuilder::palette_thresholds].
+    PaletteThresholds(Vec<f64>),
+    /// Like [ValueColor::Gradient], with the colors of the theme palette, see [DiagnosticsTextBuilder::palette_gradient].
+    PaletteGradient { min: f64, max: f64 },
+}
+
+/// The UI entities displaying a single [DiagnosticsText], spawned on layout changes.
+#[derive(Reflect, Clone, Copy)]
+struct DiagnosticsTextEntities {
+    value: Entity,
+    name: Entity,
+    graph: Option<Entity>,
 }
 
 impl DiagnosticsText {
-    fn format(&self, v: f64) -> String {
-        let formatter = self.format;
-        formatter(v)
+    fn new(name: String, path: DiagnosticPath) -> Self {
+        Self {
+            name,
+            path,
+            agg: Aggregate::Value,
+            agg_state: AggregateState::default(),
+            custom_agg: None,
+            unit: None,
+            format: None,
+            show: true,
+            show_name: true,
+            show_value: true,
+            colors: (None, None),
+            value_color: ValueColor::Fixed,
+            alerts: Vec::new(),
+            graph: None,
+            expanded: false,
+            font: None,
+            font_size: None,
+            line_height: None,
+            group: None,
+            priority: 0,
+            seq: 0,
+            edit: false,
+            rebuild: true,
+            entities: None,
+        }
+    }
+
+    /// Write the value with the formatting function, the unit or the unit matching the diagnostic suffix, in that order.
+    fn write_value(&self, v: f64, suffix: &str, s: &mut String) -> fmt::Result {
+        match (
+            &self.format,
+            self.unit
+                .map(|unit| (unit, 1.0))
+                .or_else(|| Unit::from_scaled_suffix(suffix)),
+        ) {
+            (Some(Formatter::String(format)), _) => s.write_str(&format(v)),
+            (Some(Formatter::Into(format)), _) => format(v, s),
+            (None, Some((unit, factor))) => unit.format_into(v * factor, s),
+            (None, None) => write!(s, "{v:.2}{suffix}"),
+        }
+    }
+
+    fn aggregate(&mut self, diagnostic: &Diagnostic) -> Option<f64> {
+        match &mut self.custom_agg {
+            Some(custom) => custom.aggregate(diagnostic),
+            None => self.agg.apply(diagnostic, &mut self.agg_state),
+        }
+    }
+
+    /// The value and name colors, with the ones not set taken from the theme.
+    fn colors(&self, theme: &ScreenDiagnosticsTheme) -> (Color, Color) {
+        (
+            self.colors.0.unwrap_or(theme.value_color),
+            self.colors.1.unwrap_or(theme.name_color),
+        )
+    }
+
+    fn value_color(&self, v: f64, theme: &ScreenDiagnosticsTheme) -> Color {
+        let fixed = self.colors(theme).0;
+        match &self.value_color {
+            ValueColor::Fixed => fixed,
+            ValueColor::Thresholds(thresholds) => thresholds
+                .iter()
+                .find(|(bound, _)| v < *bound)
+                .map_or(fixed, |(_, color)| *color),
+            ValueColor::Gradient { min, max, colors } => gradient(v, *min, *max, colors, fixed),
+            ValueColor::PaletteThresholds(bounds) => {
+                let i = bounds.iter().take_while(|bound| v >= **bound).count();
+                match theme.palette.get(i).or(theme.palette.last()) {
+                    Some(color) => *color,
+                    None => fixed,
+                }
+            }
+            ValueColor::PaletteGradient { min, max } => {
+                gradient(v, *min, *max, &theme.palette, fixed)
+            }
+        }
+    }
+
+    /// The [TextFont] with the overrides of this diagnostic applied.
+    fn text_font(&self, default: &TextFont) -> TextFont {
+        TextFont {
+            font: self.font.clone().unwrap_or_else(|| default.font.clone()),
+            font_size: self.font_size.unwrap_or(default.font_size),
+            line_height: self.line_height.unwrap_or(default.line_height),
+            ..default.clone()
+        }
     }
 
     fn get_name(&self) -> String {
-        match self.show_name {
-            true => format!(" {} ", self.name),
-            false => " ".to_string(),
+        let mut s = String::new();
+        self.write_name(&mut s);
+        s
+    }
+
+    fn write_name(&self, s: &mut String) {
+        s.push(' ');
+        if self.show_name {
+            s.push_str(&self.name);
+            s.push(' ');
         }
     }
 }
 
+/// Blend between the colors depending on where `v` is in the range, or `fallback` without colors.
+fn gradient(v: f64, min: f64, max: f64, colors: &[Color], fallback: Color) -> Color {
+    let Some(last) = colors.len().checked_sub(1) else {
+        return fallback;
+    };
+    let t = ((v - min) / (max - min)).clamp(0.0, 1.0);
+    let position = match t.is_nan() {
+        true => 0.0,
+        false => t as f32 * last as f32,
+    };
+    let i = (position.floor() as usize).min(last);
+    colors[i].mix(&colors[(i + 1).min(last)], position - i as f32)
+}
+
 /// Builder-like interface for a [DiagnosticsText].
 pub struct DiagnosticsTextBuilder<'a> {
-    m: &'a mut BTreeMap<String, DiagnosticsText>,
+    m: &'a mut Vec<DiagnosticsText>,
     k: String,
 }
 
 impl DiagnosticsTextBuilder<'_> {
+    fn entry(&mut self) -> Option<&mut DiagnosticsText> {
+        self.m.iter_mut().find(|e| e.name == self.k)
+    }
+
     /// Set the Aggregate function for this [DiagnosticsText]
-    pub fn aggregate(self, agg: Aggregate) -> Self {
-        self.m.entry(self.k.clone()).and_modify(|e| {
+    ///
+    /// Replaces a custom aggregate set with [DiagnosticsTextBuilder::aggregate_with].
+    pub fn aggregate(mut self, agg: Aggregate) -> Self {
+        if let Some(e) = self.entry() {
             e.agg = agg;
+            e.agg_state = AggregateState::default();
+            e.custom_agg = None;
             e.rebuild = true;
-        });
+        }
         self
     }
 
-    /// Set the formatting function for this [DiagnosticsText]
-    pub fn format(self, format: FormatFn) -> Self {
-        self.m.entry(self.k.clone()).and_modify(|e| {
-            e.format = format;
+    /// Set a custom [DiagnosticAggregator] for this [DiagnosticsText]
+    ///
+    /// ```rust
+    ///# use bevy::{diagnostic::{Diagnostic, EntityCountDiagnosticsPlugin}, prelude::*};
+    ///# use bevy_screen_diagnostics::ScreenDiagnostics;
+    ///
+    /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     screen_diagnostics
+    ///         .add("entities/s", EntityCountDiagnosticsPlugin::ENTITY_COUNT)
+    ///         // rate of change per second over the stored history
+    ///         .aggregate_with(|diagnostic: &Diagnostic| {
+    ///             let first = diagnostic.measurements().next()?;
+    ///             let last = diagnostic.measurement()?;
+    ///             let seconds = last.time.duration_since(first.time).as_secs_f64();
+    ///             (seconds > 0.0).then(|| (last.value - first.value) / seconds)
+    ///         });
+    /// }
+    /// ```
+    pub fn aggregate_with(mut self, agg: impl DiagnosticAggregator) -> Self {
+        if let Some(e) = self.entry() {
+            e.custom_agg = Some(Box::new(agg));
             e.rebuild = true;
-        });
-        self
-    }
-
-    /// Set the text color for the diagnostic value
-    pub fn diagnostic_color(self, color: Color) -> Self {
-        self.m.entry(self.k.clone()).and_modify(|e| {
-            e.colors.0 = color;
-            e.edit = true;
-        });
-        self
-    }
-
-    /// Set the text color for the diagnostic name
-    pub fn name_color(self, color: Color) -> Self {
-        self.m.entry(self.k.clone()).and_modify(|e| {
-            e.colors.1 = color;
-            e.edit = true;
-        });
+        }
         self
     }
 
-    /// Toggle whether the diagnostic name is displayed.
-    pub fn toggle_name(self) -> Self {
-        self.m.entry(self.k.clone()).and_modify(|e| {
-            e.show_name = !e.show_name;
-            e.edit = true;
-        });
+    /// Set the formatting function for this [DiagnosticsText]
+    ///
+    /// Accepts functions, closures capturing their environment and shared [FormatFn]s.
+    ///
+    /// ```rust
+    ///# use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+    ///# use bevy_screen_diagnostics::ScreenDiagnostics;
+    ///
+    /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     let precision = 3;
+    ///     screen_diagnostics
+    ///         .add("ms/frame", FrameTimeDiagnosticsPlugin::FRAME_TIME)
+    ///         .format(move |v| format!("{v:.precision$}"));
+    /// }
+    /// ```
+    ///
+    /// A [FormatFn] can be shared between diagnostics:
+    ///
+    /// ```rust
+    ///# use std::sync::Arc;
+    ///# use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+    ///# use bevy_screen_diagnostics::{FormatFn, ScreenDiagnostics};
+    ///
+    /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     let two_decimals: FormatFn = Arc::new(|v| format!("{v:.2}"));
+    ///     screen_diagnostics
+    ///         .add("ms/frame", FrameTimeDiagnosticsPlugin::FRAME_TIME)
+    ///         .format(two_decimals.clone());
+    ///     screen_diagnostics
+    ///         .add("fps", FrameTimeDiagnosticsPlugin::FPS)
+    ///         .format(two_decimals);
+    /// }
+    /// ```
+    pub fn format(mut self, format: impl IntoFormatFn) -> Self {
+        if let Some(e) = self.entry() {
+            e.format = Some(Formatter::String(format.into_format_fn()));
+            e.rebuild = true;
+        }
         self
     }
 
-    /// Toggle whether the diagnostic is displayed at all.
-    pub fn toggle(self) -> Self {
-        self.m.entry(self.k.clone()).and_modify(|e| {
-            e.show = !e.show;
+    /// Set a formatting function which writes into a reused string for this [DiagnosticsText]
+    ///
+    /// Unlike [DiagnosticsTextBuilder::format] this does not allocate a new string on every update.
+    /// Accepts functions, closures and shared [FormatIntoFn]s.
+    ///
+    /// ```rust
+    ///# use std::fmt::Write;
+    ///# use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+    ///# use bevy_screen_diagnostics::ScreenDiagnostics;
+    ///
+    /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     screen_diagnostics
+    ///         .add("fps", FrameTimeDiagnosticsPlugin::FPS)
+    ///         .format_into(|v, s: &mut String| write!(s, "{v:.0}"));
+    /// }
+    /// ```
+    pub fn format_into(mut self, format: impl IntoFormatIntoFn) -> Self {
+        if let Some(e) = self.entry() {
+            e.format = Some(Formatter::Into(format.into_format_into_fn()));
             e.rebuild = true;
-        });
+        }
         self
     }
-}
 
-impl ScreenDiagnostics {
-    /// Add a diagnostic to be displayed.
+    /// Format the value as a [Unit], scaled to a fitting prefix.
     ///
-    /// * `name` - The name displayed on-screen. Also used as a key.
-    /// * `path` - The [DiagnosticPath] which is displayed.
+    /// Replaces a formatting function set with [DiagnosticsTextBuilder::format] or [DiagnosticsTextBuilder::format_into].
+    /// Without either, the unit matching the [Diagnostic::suffix] is used if there is one.
     ///
     /// ```rust
     ///# use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
-    ///# use bevy_screen_diagnostics::{Aggregate, ScreenDiagnosticsPlugin,ScreenDiagnostics};
+    ///# use bevy_screen_diagnostics::{ScreenDiagnostics, Unit};
     ///
-    ///# fn main() {
-    ///#     App::new()
-    ///#         .add_plugins(DefaultPlugins)
-    ///#         .add_plugins(ScreenDiagnosticsPlugin::default())
-    ///#         .add_systems(Startup, setup);
-    ///# }
     /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
     ///     screen_diagnostics
-    ///         .add(
-    ///             "ms/frame".to_string(),
-    ///             FrameTimeDiagnosticsPlugin::FRAME_TIME,
-    ///         )
-    ///         .aggregate(Aggregate::Value)
-    ///         .format(|v| format!("{:.0}", v));
+    ///         .add("frame", FrameTimeDiagnosticsPlugin::FRAME_TIME)
+    ///         .unit(Unit::Milliseconds);
     /// }
     /// ```
-    pub fn add<S>(&mut self, name: S, path: DiagnosticPath) -> DiagnosticsTextBuilder
-    where
-        S: Into<String>,
-    {
-        let name: String = name.into();
-
-        let text = DiagnosticsText {
-            name: name.clone(),
-            path,
-            agg: Aggregate::Value,
-            format: |v| format!("{v:.2}"),
-            show: true,
-            show_name: true,
-            colors: (DEFAULT_COLORS.0.into(), DEFAULT_COLORS.1.into()),
-            edit: false,
-            rebuild: true,
-            index: None,
-        };
-
-        self.diagnostics.insert(name.clone(), text);
-
-        DiagnosticsTextBuilder {
-            m: &mut self.diagnostics,
-            k: name,
+    pub fn unit(mut self, unit: Unit) -> Self {
+        if let Some(e) = self.entry() {
+            e.unit = Some(unit);
+            e.format = None;
+            e.rebuild = true;
         }
+        self
     }
 
-    /// Modify a [DiagnosticsText] by name.
+    /// Set the text color for the diagnostic value, instead of the value color of the [ScreenDiagnosticsTheme]
     ///
-    /// Uses the same syntax as [ScreenDiagnostics::add]
-    pub fn modify<S>(&mut self, name: S) -> DiagnosticsTextBuilder
-    where
-        S: Into<String>,
-    {
-        DiagnosticsTextBuilder {
-            m: &mut self.diagnostics,
-            k: name.into(),
+    /// Replaces colors set with [DiagnosticsTextBuilder::thresholds] or [DiagnosticsTextBuilder::gradient].
+    pub fn diagnostic_color(mut self, color: Color) -> Self {
+        if let Some(e) = self.entry() {
+            e.colors.0 = Some(color);
+            e.value_color = ValueColor::Fixed;
+            e.edit = true;
         }
+        self
     }
 
-    /// Remove a diagnostic by name.
+    /// Color the diagnostic value depending on its aggregated value.
+    ///
+    /// Takes pairs of upper bounds and colors in ascending order. The value is colored with the
+    /// color of the first bound it is below. Values above all bounds use the color set with [DiagnosticsTextBuilder::diagnostic_color],
+    /// or the value color of the [ScreenDiagnosticsTheme].
+    ///
+    /// ```rust
+    ///# use bevy::{color::palettes::css, diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+    ///# use bevy_screen_diagnostics::ScreenDiagnostics;
+    ///
+    /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     screen_diagnostics
+    ///         .add("ms/frame", FrameTimeDiagnosticsPlugin::FRAME_TIME)
+    ///         .thresholds([
+    ///             (16.7, css::LIME.into()),
+    ///             (33.3, css::YELLOW.into()),
+    ///             (f64::INFINITY, css::RED.into()),
+    ///         ]);
+    /// }
+    /// ```
+    pub fn thresholds(mut self, thresholds: impl IntoIterator<Item = (f64, Color)>) -> Self {
+        if let Some(e) = self.entry() {
+            e.value_color = ValueColor::Thresholds(thresholds.into_iter().collect());
+            e.edit = true;
+        }
+        self
+    }
+
+    /// Color the diagnostic value by blending between colors depending on where its aggregated value is in the range.
+    ///
+    /// The colors are spread evenly over the range and mixed with [Mix], in the color space of the lower color.
+    /// Values outside of the range use the first or last color.
+    ///
+    /// ```rust
+    ///# use bevy::{color::palettes::css, diagnostic::SystemInformationDiagnosticsPlugin, prelude::*};
+    ///# use bevy_screen_diagnostics::ScreenDiagnostics;
+    ///
+    /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     screen_diagnostics
+    ///         .add("CPU", SystemInformationDiagnosticsPlugin::PROCESS_CPU_USAGE)
+    ///         .gradient(
+    ///             0.0..=100.0,
+    ///             [css::LIME.into(), css::YELLOW.into(), css::RED.into()],
+    ///         );
+    /// }
+    /// ```
+    pub fn gradient(
+        mut self,
+        range: RangeInclusive<f64>,
+        colors: impl IntoIterator<Item = Color>,
+    ) -> Self {
+        if let Some(e) = self.entry() {
+            e.value_color = ValueColor::Gradient {
+                min: *range.start(),
+                max: *range.end(),
+                colors: colors.into_iter().collect(),
+            };
+            e.edit = true;
+        }
+        self
+    }
+
+    /// Color the diagnostic value with the palette of the [ScreenDiagnosticsTheme], depending on its aggregated value.
+    ///
+    /// Takes upper bounds in ascending order, the value is colored with the palette color at the index of
+    /// the first bound it is below. Values above all bounds, or past the end of the palette, use the last color.
+    ///
+    /// ```rust
+    ///# use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+    ///# use bevy_screen_diagnostics::ScreenDiagnostics;
+    ///
+    /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     screen_diagnostics
+    ///         .add("ms/frame", FrameTimeDiagnosticsPlugin::FRAME_TIME)
+    ///         .palette_thresholds([16.7, 33.3]);
+    /// }
+    /// ```
+    pub fn palette_thresholds(mut self, bounds: impl IntoIterator<Item = f64>) -> Self {
+        if let Some(e) = self.entry() {
+            e.value_color = ValueColor::PaletteThresholds(bounds.into_iter().collect());
+            e.edit = true;
+        }
+        self
+    }
+
+    /// Color the diagnostic value by blending between the palette colors of the [ScreenDiagnosticsTheme],
+    /// like [DiagnosticsTextBuilder::gradient].
+    pub fn palette_gradient(mut self, range: RangeInclusive<f64>) -> Self {
+        if let Some(e) = self.entry() {
+            e.value_color = ValueColor::PaletteGradient {
+                min: *range.start(),
+                max: *range.end(),
+            };
+            e.edit = true;
+        }
+        self
+    }
+
+    /// Set the text color for the diagnostic name, instead of the name color of the [ScreenDiagnosticsTheme]
+    pub fn name_color(mut self, color: Color) -> Self {
+        if let Some(e) = self.entry() {
+            e.colors.1 = Some(color);
+            e.edit = true;
+        }
+        self
+    }
+
+    /// Use the colors of the [ScreenDiagnosticsTheme] again, instead of the ones set with
+    /// [DiagnosticsTextBuilder::diagnostic_color], [DiagnosticsTextBuilder::name_color],
+    /// [DiagnosticsTextBuilder::thresholds] or [DiagnosticsTextBuilder::gradient].
+    pub fn reset_colors(mut self) -> Self {
+        if let Some(e) = self.entry() {
+            e.colors = (None, None);
+            e.value_color = ValueColor::Fixed;
+            e.edit = true;
+        }
+        self
+    }
+
+    /// Toggle whether the diagnostic value is displayed.
+    ///
+    /// The name stays displayed, unlike with [DiagnosticsTextBuilder::toggle].
+    pub fn toggle_value(mut self) -> Self {
+        if let Some(e) = self.entry() {
+            e.show_value = !e.show_value;
+            e.rebuild = true;
+        }
+        self
+    }
+
+    /// Toggle whether the diagnostic name is displayed.
+    pub fn toggle_name(mut self) -> Self {
+        if let Some(e) = self.entry() {
+            e.show_name = !e.show_name;
+            e.edit = true;
+        }
+        self
+    }
+
+    /// Raise a [DiagnosticAlert] when the aggregated value crosses the threshold.
+    ///
+    /// Can be called multiple times to add multiple thresholds.
+    /// Alerts are also raised while the diagnostic is hidden.
+    ///
+    /// ```rust
+    ///# use std::time::Duration;
+    ///# use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+    ///# use bevy_screen_diagnostics::{AlertThreshold, ScreenDiagnostics};
+    ///
+    /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     screen_diagnostics
+    ///         .add("fps", FrameTimeDiagnosticsPlugin::FPS)
+    ///         .alert(
+    ///             AlertThreshold::below(30.0)
+    ///                 .with_hysteresis(5.0)
+    ///                 .with_duration(Duration::from_secs(2)),
+    ///         );
+    /// }
+    /// ```
+    pub fn alert(mut self, threshold: AlertThreshold) -> Self {
+        if let Some(e) = self.entry() {
+            e.alerts.push(Alert::new(threshold));
+        }
+        self
+    }
+
+    /// Remove all thresholds added with [DiagnosticsTextBuilder::alert].
+    pub fn clear_alerts(mut self) -> Self {
+        if let Some(e) = self.entry() {
+            e.alerts.clear();
+        }
+        self
+    }
+
+    /// Display a [Sparkline] of the diagnostic history next to the value.
+    ///
+    /// ```rust
+    ///# use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+    ///# use bevy_screen_diagnostics::{ScreenDiagnostics, Sparkline};
+    ///
+    /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     screen_diagnostics
+    ///         .add("ms/frame", FrameTimeDiagnosticsPlugin::FRAME_TIME)
+    ///         .graph(Sparkline {
+    ///             samples: 60,
+    ///             ..default()
+    ///         });
+    /// }
+    /// ```
+    pub fn graph(mut self, graph: Sparkline) -> Self {
+        if let Some(e) = self.entry() {
+            e.graph = Some(graph);
+            e.expanded = true;
+            e.rebuild = true;
+        }
+        self
+    }
+
+    /// Remove the [Sparkline] set with [DiagnosticsTextBuilder::graph].
+    pub fn remove_graph(mut self) -> Self {
+        if let Some(e) = self.entry() {
+            e.graph = None;
+            e.expanded = false;
+            e.rebuild = true;
+        }
+        self
+    }
+
+    /// Toggle whether the [Sparkline] is displayed next to the value.
+    ///
+    /// Uses the default [Sparkline] if none was set with [DiagnosticsTextBuilder::graph].
+    pub fn toggle_graph(mut self) -> Self {
+        if let Some(e) = self.entry() {
+            e.expanded = !e.expanded;
+            if e.expanded && e.graph.is_none() {
+                e.graph = Some(Sparkline::default());
+            }
+            e.rebuild = true;
+        }
+        self
+    }
+
+    /// Display this [DiagnosticsText] in the named group, below the group header.
+    ///
+    /// Groups are displayed after the ungrouped diagnostics, in the order they were first used.
+    /// Use [ScreenDiagnostics::group] to configure the group.
+    ///
+    /// ```rust
+    ///# use bevy::{diagnostic::EntityCountDiagnosticsPlugin, prelude::*};
+    ///# use bevy_screen_diagnostics::ScreenDiagnostics;
+    ///
+    /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     screen_diagnostics
+    ///         .add("entities", EntityCountDiagnosticsPlugin::ENTITY_COUNT)
+    ///         .group("world");
+    ///     screen_diagnostics.group("world").collapse();
+    /// }
+    /// ```
+    pub fn group<S>(mut self, group: S) -> Self
+    where
+        S: Into<String>,
+    {
+        if let Some(e) = self.entry() {
+            e.group = Some(group.into());
+            e.rebuild = true;
+        }
+        self
+    }
+
+    /// Remove this [DiagnosticsText] from its group.
+    pub fn ungroup(mut self) -> Self {
+        if let Some(e) = self.entry() {
+            e.group = None;
+            e.rebuild = true;
+        }
+        self
+    }
+
+    /// Set the priority of this [DiagnosticsText]. Default: 0
+    ///
+    /// Diagnostics with a higher priority are displayed first,
+    /// ones with the same priority in the order they were added.
+    pub fn priority(mut self, priority: i32) -> Self {
+        if let Some(e) = self.entry() {
+            e.priority = priority;
+            e.rebuild = true;
+        }
+        self
+    }
+
+    /// Set the font of this [DiagnosticsText], instead of the one set for all diagnostics.
+    pub fn font(mut self, font: Handle<Font>) -> Self {
+        if let Some(e) = self.entry() {
+            e.font = Some(font);
+            e.rebuild = true;
+        }
+        self
+    }
+
+    /// Set the font size of this [DiagnosticsText], instead of the one set for all diagnostics.
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        if let Some(e) = self.entry() {
+            e.font_size = Some(font_size);
+            e.rebuild = true;
+        }
+        self
+    }
+
+    /// Set the line height of this [DiagnosticsText], instead of the one set for all diagnostics.
+    pub fn line_height(mut self, line_height: LineHeight) -> Self {
+        if let Some(e) = self.entry() {
+            e.line_height = Some(line_height);
+            e.rebuild = true;
+        }
+        self
+    }
+
+    /// Toggle whether the diagnostic is displayed at all.
+    pub fn toggle(mut self) -> Self {
+        if let Some(e) = self.entry() {
+            e.show = !e.show;
+            e.rebuild = true;
+        }
+        self
+    }
+}
+
+/// The display order: by priority, then in the order they were added.
+fn display_order(text: &DiagnosticsText) -> (Reverse<i32>, usize) {
+    (Reverse(text.priority), text.seq)
+}
+
+/// Sort the diagnostics into display order.
+fn sort_for_display(diagnostics: &mut [DiagnosticsText]) {
+    diagnostics.sort_by_key(display_order);
+}
+
+/// Add a [DiagnosticsText], replacing an existing one with the same name in place, keeping its position.
+fn add_text(
+    diagnostics: &mut Vec<DiagnosticsText>,
+    name: String,
+    path: DiagnosticPath,
+) -> DiagnosticsTextBuilder<'_> {
+    let mut text = DiagnosticsText::new(name.clone(), path);
+    match diagnostics.iter_mut().find(|e| e.name == name) {
+        Some(existing) => {
+            text.seq = existing.seq;
+            *existing = text;
+        }
+        None => {
+            text.seq = diagnostics.iter().map(|e| e.seq + 1).max().unwrap_or(0);
+            diagnostics.push(text);
+        }
+    }
+    DiagnosticsTextBuilder {
+        m: diagnostics,
+        k: name,
+    }
+}
+
+impl<M> ScreenDiagnostics<M> {
+    /// Add a diagnostic to be displayed.
+    ///
+    /// * `name` - The name displayed on-screen. Also used as a key.
+    /// * `path` - The [DiagnosticPath] which is displayed.
+    ///
+    /// ```rust
+    ///# use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+    ///# use bevy_screen_diagnostics::{Aggregate, ScreenDiagnosticsPlugin,ScreenDiagnostics};
+    ///
+    ///# fn main() {
+    ///#     App::new()
+    ///#         .add_plugins(DefaultPlugins)
+    ///#         .add_plugins(ScreenDiagnosticsPlugin::default())
+    ///#         .add_systems(Startup, setup);
+    ///# }
+    /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     screen_diagnostics
+    ///         .add(
+    ///             "ms/frame".to_string(),
+    ///             FrameTimeDiagnosticsPlugin::FRAME_TIME,
+    ///         )
+    ///         .aggregate(Aggregate::Value)
+    ///         .format(|v| format!("{:.0}", v));
+    /// }
+    /// ```
+    pub fn add<S>(&mut self, name: S, path: DiagnosticPath) -> DiagnosticsTextBuilder<'_>
+    where
+        S: Into<String>,
+    {
+        add_text(&mut self.diagnostics, name.into(), path)
+    }
+
+    /// Modify a [DiagnosticsText] by name.
+    ///
+    /// Uses the same syntax as [ScreenDiagnostics::add]
+    pub fn modify<S>(&mut self, name: S) -> DiagnosticsTextBuilder<'_>
+    where
+        S: Into<String>,
+    {
+        DiagnosticsTextBuilder {
+            m: &mut self.diagnostics,
+            k: name.into(),
+        }
+    }
+
+    /// Remove a diagnostic by name.
     #[allow(dead_code)]
     pub fn remove(&mut self, name: String) {
-        self.diagnostics.remove(&name);
+        self.diagnostics.retain(|e| e.name != name);
+        self.layout_changed = true;
+    }
+
+    /// Move a diagnostic to the position in the display order and trigger a rebuild.
+    ///
+    /// Diagnostics are displayed in the order they were added, but ones with a higher
+    /// [DiagnosticsTextBuilder::priority] always come first, so this only moves it among
+    /// diagnostics with the same priority. Positions past the end move it to the end.
+    ///
+    /// ```rust
+    ///# use bevy::prelude::*;
+    ///# use bevy_screen_diagnostics::ScreenDiagnostics;
+    ///
+    /// fn fps_first(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     screen_diagnostics.move_to("fps", 0);
+    /// }
+    /// ```
+    pub fn move_to<S>(&mut self, name: S, position: usize)
+    where
+        S: Into<String>,
+    {
+        let name: String = name.into();
+        sort_for_display(&mut self.diagnostics);
+        if let Some(index) = self.diagnostics.iter().position(|e| e.name == name) {
+            let text = self.diagnostics.remove(index);
+            let position = position.min(self.diagnostics.len());
+            self.diagnostics.insert(position, text);
+            // the new order replaces the insertion order
+            for (seq, text) in self.diagnostics.iter_mut().enumerate() {
+                text.seq = seq;
+            }
+            self.layout_changed = true;
+        }
+    }
+
+    /// The names of all diagnostics in display order, including hidden ones.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        let mut texts: Vec<&DiagnosticsText> = self.diagnostics.iter().collect();
+        texts.sort_by_key(|text| display_order(text));
+        texts.into_iter().map(|e| e.name.as_str())
+    }
+
+    /// Configure a group of diagnostics by name, creating it if it doesn't exist yet.
+    ///
+    /// Add diagnostics to the group with [DiagnosticsTextBuilder::group].
+    pub fn group<S>(&mut self, name: S) -> DiagnosticsGroupBuilder<'_>
+    where
+        S: Into<String>,
+    {
+        let name: String = name.into();
+        let index = match self.groups.iter().position(|g| g.name == name) {
+            Some(index) => index,
+            None => {
+                self.groups.push(DiagnosticsGroup::new(name));
+                self.groups.len() - 1
+            }
+        };
+        DiagnosticsGroupBuilder {
+            group: &mut self.groups[index],
+            layout_changed: &mut self.layout_changed,
+        }
+    }
+
+    /// Set the [DiagnosticsLayout] and trigger a rebuild
+    pub fn set_layout(&mut self, layout: DiagnosticsLayout) {
+        self.layout = layout;
+        self.layout_changed = true;
+    }
+
+    /// Set the font of all diagnostics and trigger a rebuild
+    ///
+    /// Replaces the font set with [ScreenDiagnosticsPlugin::font].
+    pub fn set_font(&mut self, font: Handle<Font>) {
+        self.font = font;
+        self.layout_changed = true;
+    }
+
+    /// Set the font size of all diagnostics and trigger a rebuild
+    ///
+    /// Replaces the font size of the [ScreenDiagnosticsTheme].
+    pub fn set_font_size(&mut self, font_size: f32) {
+        self.font_size = Some(font_size);
+        self.layout_changed = true;
+    }
+
+    /// Set the line height of all diagnostics and trigger a rebuild
+    pub fn set_line_height(&mut self, line_height: LineHeight) {
+        self.line_height = line_height;
+        self.layout_changed = true;
+    }
+
+    /// Set the [DiagnosticsBackground] and trigger a rebuild
+    ///
+    /// Replaces the background of the [ScreenDiagnosticsTheme].
+    pub fn set_background(&mut self, background: DiagnosticsBackground) {
+        self.background = Some(background);
+        self.layout_changed = true;
+    }
+
+    /// Move the diagnostics to the [DiagnosticsAnchor] and trigger a rebuild
+    ///
+    /// Replaces the position set with [ScreenDiagnosticsPlugin::style].
+    ///
+    /// ```rust
+    ///# use bevy::prelude::*;
+    ///# use bevy_screen_diagnostics::{DiagnosticsAnchor, ScreenDiagnostics};
+    ///
+    /// fn move_out_of_the_way(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     screen_diagnostics.set_anchor(DiagnosticsAnchor::TopRight);
+    /// }
+    /// ```
+    pub fn set_anchor(&mut self, anchor: DiagnosticsAnchor) {
+        self.anchor = Some(anchor);
+        self.layout_changed = true;
+    }
+
+    /// The [DiagnosticsAnchor] set with [ScreenDiagnostics::set_anchor] or [ScreenDiagnosticsPlugin::anchor].
+    pub fn anchor(&self) -> Option<DiagnosticsAnchor> {
+        self.anchor
+    }
+
+    /// Set the distance in pixels from the edges of the screen when using an anchor and trigger a rebuild
+    pub fn set_margin(&mut self, margin: Vec2) {
+        self.margin = margin;
+        self.layout_changed = true;
+    }
+
+    /// Keep the anchored diagnostics inside the [SafeArea] and trigger a rebuild
+    pub fn set_safe_area(&mut self, safe_area: SafeArea) {
+        self.safe_area = safe_area;
+        self.layout_changed = true;
+    }
+
+    /// Display the diagnostics on the camera instead of the default UI camera and trigger a rebuild
+    ///
+    /// `None` moves them back to the default UI camera, see [ScreenDiagnosticsPlugin::target_camera].
+    ///
+    /// ```rust
+    ///# use bevy::prelude::*;
+    ///# use bevy_screen_diagnostics::ScreenDiagnostics;
+    ///
+    /// fn setup(mut commands: Commands, mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     let camera = commands.spawn(Camera2d).id();
+    ///     screen_diagnostics.set_target_camera(Some(camera));
+    /// }
+    /// ```
+    pub fn set_target_camera(&mut self, camera: Option<Entity>) {
+        self.target_camera = camera;
+        self.layout_changed = true;
+    }
+
+    /// The camera set with [ScreenDiagnostics::set_target_camera] or [ScreenDiagnosticsPlugin::target_camera].
+    pub fn target_camera(&self) -> Option<Entity> {
+        self.target_camera
     }
 
     /// Set the [JustifyText] and trigger a rebuild
@@ -350,113 +1444,715 @@ impl ScreenDiagnostics {
         self.text_alignment = align;
         self.layout_changed = true;
     }
+
+    /// Show or hide the whole panel and trigger a rebuild
+    ///
+    /// Unlike [DiagnosticsTextBuilder::toggle] this keeps whether each diagnostic is shown.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        self.layout_changed = true;
+    }
+
+    /// Toggle whether the whole panel is shown, see [ScreenDiagnostics::set_visible].
+    pub fn toggle_visible(&mut self) {
+        self.set_visible(!self.visible);
+    }
+
+    /// Whether the panel is shown, see [ScreenDiagnostics::set_visible].
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Add a preset of diagnostics by name, like a verbosity level. Returns its index.
+    ///
+    /// While a preset is active, only the diagnostics in it are displayed.
+    /// Diagnostics hidden with [DiagnosticsTextBuilder::toggle] stay hidden.
+    ///
+    /// ```rust
+    ///# use bevy::prelude::*;
+    ///# use bevy_screen_diagnostics::ScreenDiagnostics;
+    ///
+    /// fn setup(mut screen_diagnostics: ResMut<ScreenDiagnostics>) {
+    ///     // only the framerate, then everything again
+    ///     screen_diagnostics.add_preset(["fps"]);
+    /// }
+    /// ```
+    pub fn add_preset<I, S>(&mut self, names: I) -> usize
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.presets
+            .push(names.into_iter().map(Into::into).collect());
+        self.presets.len() - 1
+    }
+
+    /// Activate the preset at the index, or display all diagnostics when `None`, and trigger a rebuild
+    pub fn set_preset(&mut self, preset: Option<usize>) {
+        self.preset = preset.filter(|i| *i < self.presets.len());
+        self.layout_changed = true;
+    }
+
+    /// Activate the next preset in the order they were added, displaying all diagnostics after the last one.
+    pub fn cycle_preset(&mut self) {
+        let next = match self.preset {
+            Some(i) => i + 1,
+            None => 0,
+        };
+        self.set_preset(Some(next));
+    }
+
+    /// The index of the active preset, see [ScreenDiagnostics::add_preset].
+    pub fn preset(&self) -> Option<usize> {
+        self.preset
+    }
+
+    /// Show the names of all diagnostics if none are shown, otherwise hide all of them.
+    pub fn toggle_names(&mut self) {
+        let show_name = !self.diagnostics.iter().any(|e| e.show_name);
+        for e in self.diagnostics.iter_mut() {
+            e.show_name = show_name;
+            e.edit = true;
+        }
+    }
+
+    /// Move the diagnostics to the next [DiagnosticsAnchor] and trigger a rebuild
+    ///
+    /// Without an anchor this starts after the default [DiagnosticsAnchor::BottomRight].
+    pub fn cycle_anchor(&mut self) {
+        self.set_anchor(self.anchor.unwrap_or_default().next());
+    }
+
+    /// Set the [DiagnosticsKeys] which control the diagnostics
+    ///
+    /// Replaces the keys set with [ScreenDiagnosticsPlugin::keys].
+    pub fn set_keys(&mut self, keys: DiagnosticsKeys) {
+        self.keys = keys;
+    }
+
+    /// Set what clicking the name of a diagnostic does, or make them non-interactive with `None`, and trigger a rebuild
+    ///
+    /// Replaces the action set with [ScreenDiagnosticsPlugin::click].
+    pub fn set_click(&mut self, click: Option<DiagnosticsClick>) {
+        self.click = click;
+        self.layout_changed = true;
+    }
 }
 
-fn spawn_ui(
+/// Spawn the panel, again if it was despawned, like with a state scoped UI.
+fn spawn_ui<M: Send + Sync + 'static>(
+    mut diags: ResMut<ScreenDiagnostics<M>>,
+    panel: Query<(), With<DiagnosticsPanelMarker<M>>>,
     mut commands: Commands,
-    diag_style: Res<DiagnosticsStyle>,
-    diag_layer: Res<DiagnosticsLayer>,
 ) {
+    if !panel.is_empty() {
+        return;
+    }
+    // the background, anchor and diagnostics are applied on the next panel update
+    diags.layout_changed = true;
     commands.spawn((
-        Text::default(),
-        diag_style.0.clone(),
-        diag_layer.clone(),
-        DiagnosticsTextMarker,
+        diags.style.clone(),
+        diags.render_layer.clone(),
+        DiagnosticsPanelMarker::<M>(PhantomData),
+        children![
+            (
+                diags.render_layer.clone(),
+                DiagnosticsTextMarker::<M>(PhantomData)
+            ),
+            (
+                Node {
+                    position_type: PositionType::Absolute,
+                    display: Display::None,
+                    ..default()
+                },
+                diags.render_layer.clone(),
+                DiagnosticsTooltip::<M>::default()
+            )
+        ],
     ));
 }
 
-fn update_onscreen_diags_layout(
-    mut diags: ResMut<ScreenDiagnostics>,
-    font: Res<ScreenDiagnosticsFont>,
-    mut text_layout: Single<(Entity, &mut TextLayout), With<DiagnosticsTextMarker>>,
+type PanelStyle<'a> = (
+    &'a mut Node,
+    &'a mut BackgroundColor,
+    &'a mut BorderColor,
+    &'a mut BorderRadius,
+);
+
+/// Apply the background, position, visibility and target camera to the panel.
+fn update_panel<M: Send + Sync + 'static>(
+    diags: Res<ScreenDiagnostics<M>>,
+    theme: Res<ScreenDiagnosticsTheme>,
+    ui_scale: Res<UiScale>,
+    panel: Single<(Entity, PanelStyle), With<DiagnosticsPanelMarker<M>>>,
     mut commands: Commands,
 ) {
-    if diags.layout_changed {
-        commands.entity(text_layout.0).remove::<Children>();
-
-        for (i, text) in diags
-            .diagnostics
-            .values_mut()
-            .rev()
-            .filter(|t| t.show)
-            .enumerate()
-        {
-            text.index = Some(i * 2 + 1);
-            commands.entity(text_layout.0).with_children(|c| {
-                c.spawn((
-                    TextSpan::new("test_val"),
-                    TextFont::from_font(font.0.clone()).with_font_size(20.0),
-                    TextColor(text.colors.0),
-                ));
-                c.spawn((
-                    TextSpan::new(text.get_name()),
-                    TextFont::from_font(font.0.clone()).with_font_size(20.0),
-                    TextColor(text.colors.1),
-                ));
-            });
+    if diags.layout_changed || theme.is_changed() || ui_scale.is_changed() {
+        let (entity, (mut node, mut color, mut border_color, mut border_radius)) =
+            panel.into_inner();
+        match diags.target_camera {
+            Some(camera) => commands.entity(entity).insert(UiTargetCamera(camera)),
+            None => commands.entity(entity).remove::<UiTargetCamera>(),
+        };
+        let background = diags.background.as_ref().unwrap_or(&theme.background);
+        node.padding = background.padding;
+        node.border = background.border;
+        color.0 = background.color;
+        border_color.0 = background.border_color;
+        *border_radius = background.border_radius;
+        node.display = match diags.visible {
+            true => diags.style.display,
+            false => Display::None,
+        };
+        if let Some(anchor) = diags.anchor {
+            anchor.apply(&mut node, diags.margin, &diags.safe_area, ui_scale.0);
         }
+    }
+}
+
+fn update_onscreen_diags_layout<M: Send + Sync + 'static>(
+    mut diags: ResMut<ScreenDiagnostics<M>>,
+    theme: Res<ScreenDiagnosticsTheme>,
+    mut root: Single<(Entity, &mut Node), With<DiagnosticsTextMarker<M>>>,
+    mut commands: Commands,
+) {
+    // rebuild on theme changes, to restyle everything which isn't overridden
+    if diags.layout_changed || theme.is_changed() {
+        commands.entity(root.0).despawn_related::<Children>();
+
+        let ScreenDiagnostics {
+            render_layer: layer,
+            text_alignment,
+            layout,
+            click,
+            font,
+            font_size,
+            line_height,
+            diagnostics,
+            groups,
+            presets,
+            preset,
+            ..
+        } = &mut *diags;
+        let preset = preset.and_then(|i| presets.get(i));
+
+        sort_for_display(diagnostics);
+        for text in diagnostics.iter_mut() {
+            text.entities = None;
+            if let Some(group) = &text.group
+                && !groups.iter().any(|g| &g.name == group)
+            {
+                groups.push(DiagnosticsGroup::new(group.clone()));
+            }
+        }
+
+        let (align, justify, justify_header) = match text_alignment {
+            JustifyText::Left => (
+                AlignItems::FlexStart,
+                JustifyContent::FlexStart,
+                JustifySelf::Start,
+            ),
+            JustifyText::Center => (
+                AlignItems::Center,
+                JustifyContent::Center,
+                JustifySelf::Center,
+            ),
+            JustifyText::Right => (
+                AlignItems::FlexEnd,
+                JustifyContent::FlexEnd,
+                JustifySelf::End,
+            ),
+            JustifyText::Justified => (
+                AlignItems::Stretch,
+                JustifyContent::SpaceBetween,
+                JustifySelf::Stretch,
+            ),
+        };
+        let text_font = TextFont {
+            font: font.clone(),
+            font_size: font_size.unwrap_or(theme.font_size),
+            line_height: *line_height,
+            ..default()
+        };
 
-        *text_layout.1 = TextLayout {
-            justify: diags.text_alignment,
-            linebreak: LineBreak::WordBoundary,
+        // a single grid for all groups, so the columns line up across them
+        let table = match layout {
+            DiagnosticsLayout::Inline => None,
+            DiagnosticsLayout::Table { value_width } => Some(
+                commands
+                    .spawn((
+                        Node {
+                            display: Display::Grid,
+                            grid_template_columns: vec![
+                                GridTrack::minmax(
+                                    MinTrackSizingFunction::Px(*value_width),
+                                    MaxTrackSizingFunction::MaxContent,
+                                ),
+                                GridTrack::auto(),
+                                GridTrack::auto(),
+                            ],
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        layer.clone(),
+                        ChildOf(root.0),
+                    ))
+                    .id(),
+            ),
         };
 
+        // the ungrouped diagnostics first, then each group below its header
+        for group in iter::once(None).chain(groups.iter().map(Some)) {
+            let group_name = group.map(|g| g.name.as_str());
+            let in_section = |text: &DiagnosticsText| {
+                text.show
+                    && text.group.as_deref() == group_name
+                    && preset.is_none_or(|names| names.contains(&text.name))
+            };
+            if !diagnostics.iter().any(in_section) {
+                continue;
+            }
+            let header = group.map(|group| {
+                (
+                    Text::new(group.header()),
+                    text_font.clone(),
+                    TextColor(group.color.unwrap_or(theme.name_color)),
+                    layer.clone(),
+                )
+            });
+            let collapsed = group.is_some_and(|group| group.collapsed);
+            let mut spawn_rows = |rows: &mut ChildSpawnerCommands| {
+                for text in diagnostics.iter_mut().filter(|t| in_section(t)) {
+                    let entities = spawn_row(rows, text, &text_font, &theme, layer, *layout);
+                    if click.is_some() {
+                        rows.commands()
+                            .entity(entities.name)
+                            .insert(Interaction::default());
+                    }
+                    text.entities = Some(entities);
+                }
+            };
+            match table {
+                Some(grid) => {
+                    commands.entity(grid).with_children(|cells| {
+                        if let Some(header) = header {
+                            // the header spans the whole row
+                            cells.spawn((
+                                header,
+                                Node {
+                                    grid_column: GridPlacement::span(3),
+                                    justify_self: justify_header,
+                                    ..default()
+                                },
+                            ));
+                        }
+                        if !collapsed {
+                            spawn_rows(cells);
+                        }
+                    });
+                }
+                None => {
+                    commands.entity(root.0).with_children(|c| {
+                        c.spawn((
+                            Node {
+                                flex_direction: FlexDirection::Column,
+                                align_items: align,
+                                ..default()
+                            },
+                            layer.clone(),
+                        ))
+                        .with_children(|section| {
+                            if let Some(header) = header {
+                                section.spawn(header);
+                            }
+                            if !collapsed {
+                                section
+                                    .spawn((
+                                        Node {
+                                            flex_wrap: FlexWrap::Wrap,
+                                            align_items: AlignItems::Center,
+                                            justify_content: justify,
+                                            ..default()
+                                        },
+                                        layer.clone(),
+                                    ))
+                                    .with_children(spawn_rows);
+                            }
+                        });
+                    });
+                }
+            }
+        }
+
+        root.1.flex_direction = FlexDirection::Column;
+        root.1.align_items = align;
+
         diags.layout_changed = false;
     }
 }
 
-fn update_diags(
-    mut diag: ResMut<ScreenDiagnostics>,
-    diagnostics: Res<DiagnosticsStore>,
-    root_text: Single<Entity, With<DiagnosticsTextMarker>>,
-    mut writer: TextUiWriter,
+/// Spawn the value, graph and name of a diagnostic in a row.
+///
+/// In the table layout these are spawned as cells of the grid directly.
+fn spawn_row(
+    rows: &mut ChildSpawnerCommands,
+    text: &DiagnosticsText,
+    text_font: &TextFont,
+    theme: &ScreenDiagnosticsTheme,
+    layer: &RenderLayers,
+    layout: DiagnosticsLayout,
+) -> DiagnosticsTextEntities {
+    match layout {
+        DiagnosticsLayout::Inline => {
+            let mut entities = DiagnosticsTextEntities {
+                value: Entity::PLACEHOLDER,
+                name: Entity::PLACEHOLDER,
+                graph: None,
+            };
+            rows.spawn((
+                Node {
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                layer.clone(),
+            ))
+            .with_children(|row| {
+                entities = spawn_cells(row, text, text_font, theme, layer, false);
+            });
+            entities
+        }
+        DiagnosticsLayout::Table { .. } => spawn_cells(rows, text, text_font, theme, layer, true),
+    }
+}
+
+fn spawn_cells(
+    parent: &mut ChildSpawnerCommands,
+    text: &DiagnosticsText,
+    text_font: &TextFont,
+    theme: &ScreenDiagnosticsTheme,
+    layer: &RenderLayers,
+    table: bool,
+) -> DiagnosticsTextEntities {
+    let text_font = text.text_font(text_font);
+    let (value_color, name_color) = text.colors(theme);
+    let value = parent
+        .spawn((
+            Text::default(),
+            Node {
+                justify_self: match table {
+                    true => JustifySelf::End,
+                    false => JustifySelf::Auto,
+                },
+                // hidden values keep their cell in the table
+                display: match text.show_value || table {
+                    true => Display::Flex,
+                    false => Display::None,
+                },
+                ..default()
+            },
+            match text.show_value {
+                true => Visibility::Inherited,
+                false => Visibility::Hidden,
+            },
+            text_font.clone(),
+            TextColor(value_color),
+            layer.clone(),
+        ))
+        .id();
+    let graph = text
+        .graph
+        .as_ref()
+        .filter(|_| text.expanded)
+        .map(|graph| graph.spawn(parent, layer, value_color));
+    if table && graph.is_none() {
+        // keep the names in their column
+        parent.spawn((Node::default(), layer.clone()));
+    }
+    let name = parent
+        .spawn((
+            Text::new(text.get_name()),
+            text_font.clone(),
+            TextColor(name_color),
+            layer.clone(),
+        ))
+        .id();
+    DiagnosticsTextEntities { value, name, graph }
+}
+
+fn update_diags<M: Send + Sync + 'static>(
+    mut diag: ResMut<ScreenDiagnostics<M>>,
+    mut updater: DiagnosticsUpdater,
 ) -> Result {
-    if diag.layout_changed {
-        return Ok(());
+    // also while the layout waits to be rebuilt, so alerts don't depend on the UI
+    if updater.update(&mut diag.diagnostics) {
+        diag.layout_changed = true;
     }
-    let mut layout_changed = false;
-    for text_diag in diag.diagnostics.values_mut().rev() {
-        if text_diag.rebuild {
-            layout_changed = true;
-            text_diag.rebuild = false;
-            continue;
-        }
-        // needs to be checked here otherwise this tries to edit bad texts
-        if !text_diag.show {
-            continue;
-        }
+    Ok(())
+}
 
-        if let Some(index) = text_diag.index {
-            if text_diag.edit {
-                // set the value color
-                *writer.color(root_text.entity(), index) = text_diag.colors.0.into();
-                // set the name color
-                *writer.color(root_text.entity(), index + 1) = text_diag.colors.1.into();
+/// Everything needed to update the displayed values of [DiagnosticsText]s.
+#[derive(SystemParam)]
+struct DiagnosticsUpdater<'w, 's> {
+    store: Res<'w, DiagnosticsStore>,
+    theme: Res<'w, ScreenDiagnosticsTheme>,
+    bars: SparklineBars<'w, 's>,
+    writer: TextUiWriter<'w, 's>,
+    alerts: AlertWriter<'w, 's>,
+    buffer: Local<'s, String>,
+}
 
-                // toggle the name visibility
-                *writer.text(root_text.entity(), index + 1) = text_diag.get_name();
+impl DiagnosticsUpdater<'_, '_> {
+    /// Aggregate and display the values, returning whether the layout has to be rebuilt.
+    fn update(&mut self, texts: &mut [DiagnosticsText]) -> bool {
+        let mut layout_changed = false;
+        for text_diag in texts.iter_mut() {
+            if text_diag.rebuild {
+                layout_changed = true;
+                text_diag.rebuild = false;
+                continue;
+            }
 
+            // hidden diagnostics have no entities, but are still aggregated for their alerts.
+            // the entities are dropped when they were despawned, like with a state scoped UI,
+            // and spawned again by rebuilding the layout, after respawning the panel if it is gone
+            if let Some(entities) = text_diag.entities
+                && text_diag.edit
+            {
+                if self.edit(text_diag, entities).is_none() {
+                    text_diag.entities = None;
+                    layout_changed = true;
+                }
                 text_diag.edit = false;
             }
-        }
 
-        if let Some(diag_val) = diagnostics.get(&text_diag.path) {
-            let diag_val = match text_diag.agg {
-                Aggregate::Value => diag_val.value(),
-                Aggregate::Average => diag_val.average(),
-                Aggregate::MovingAverage(count) => {
-                    let skip_maybe = diag_val.history_len().checked_sub(count);
-                    skip_maybe.map(|skip| diag_val.values().skip(skip).sum::<f64>() / count as f64)
+            if let Some(diagnostic) = self.store.get(&text_diag.path) {
+                if let Some(graph) = &text_diag.graph
+                    && let Some(graph_entity) = text_diag.entities.and_then(|e| e.graph)
+                {
+                    graph.update(
+                        diagnostic,
+                        graph_entity,
+                        &mut self.bars,
+                        text_diag.colors(&self.theme).0,
+                    );
                 }
-            };
 
-            if let Some(val) = diag_val {
-                if let Some(index) = text_diag.index {
-                    *writer.text(root_text.entity(), index) = text_diag.format(val);
+                let Some(val) = text_diag.aggregate(diagnostic) else {
+                    continue;
+                };
+
+                if let Some(measurement) = diagnostic.measurement() {
+                    for alert in &mut text_diag.alerts {
+                        if let Some(state) = alert.update(val, measurement.time) {
+                            let alert = DiagnosticAlert {
+                                name: text_diag.name.clone(),
+                                path: text_diag.path.clone(),
+                                value: val,
+                                threshold: alert.threshold(),
+                                state,
+                            };
+                            self.alerts.write(alert);
+                        }
+                    }
+                }
+
+                self.buffer.clear();
+                let written = text_diag.entities.is_some()
+                    && text_diag
+                        .write_value(val, &diagnostic.suffix, &mut self.buffer)
+                        .is_ok();
+                if let Some(entities) = text_diag.entities
+                    && self.display(text_diag, entities, val, written).is_none()
+                {
+                    text_diag.entities = None;
+                    layout_changed = true;
                 }
             }
         }
+        layout_changed
+    }
+
+    /// Apply the colors and name visibility, `None` if the entities were despawned with the UI.
+    fn edit(&mut self, text: &DiagnosticsText, entities: DiagnosticsTextEntities) -> Option<()> {
+        let (value_color, name_color) = text.colors(&self.theme);
+        // set the value color
+        *self.writer.get_color(entities.value, 0)? = value_color.into();
+        // set the name color
+        *self.writer.get_color(entities.name, 0)? = name_color.into();
+
+        // toggle the name visibility
+        self.buffer.clear();
+        text.write_name(&mut self.buffer);
+        set_text_if_changed(self.writer.get_text(entities.name, 0)?, &self.buffer);
+        Some(())
+    }
+
+    /// Display the aggregated value, written to the buffer if `written`,
+    /// `None` if the entities were despawned with the UI.
+    fn display(
+        &mut self,
+        text: &DiagnosticsText,
+        entities: DiagnosticsTextEntities,
+        val: f64,
+        written: bool,
+    ) -> Option<()> {
+        if written {
+            set_text_if_changed(self.writer.get_text(entities.value, 0)?, &self.buffer);
+        }
+
+        let color = text.value_color(val, &self.theme);
+        let mut value_color = self.writer.get_color(entities.value, 0)?;
+        if value_color.0 != color {
+            value_color.0 = color;
+        }
+        Some(())
+    }
+}
+
+/// Only touch the text when it changed, so unchanged text is not laid out again.
+fn set_text_if_changed(mut text: Mut<String>, new: &str) {
+    if *text != new {
+        text.clear();
+        text.push_str(new);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    const PATH: DiagnosticPath = DiagnosticPath::const_new("test");
+
+    fn names(diags: &ScreenDiagnostics) -> Vec<&str> {
+        diags.names().collect()
+    }
+
+    #[test]
+    fn priority_keeps_insertion_order() {
+        let mut diags = ScreenDiagnostics::default();
+        diags.add("a", PATH);
+        diags.add("b", PATH);
+        diags.add("c", PATH).priority(1);
+        assert_eq!(names(&diags), ["c", "a", "b"]);
+
+        // back in the order it was added
+        diags.modify("c").priority(0);
+        assert_eq!(names(&diags), ["a", "b", "c"]);
+
+        // replacing keeps the position
+        diags.add("a", PATH);
+        assert_eq!(names(&diags), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn panel_overrides_theme() {
+        struct Second;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_plugins(ScreenDiagnosticsPlugin {
+                theme: ScreenDiagnosticsTheme::dark(),
+                ..default()
+            })
+            .add_plugins(
+                ScreenDiagnosticsPlugin {
+                    font_size: Some(30.0),
+                    ..default()
+                }
+                .with_panel::<Second>(),
+            );
+
+        let first = app.world().resource::<ScreenDiagnostics>();
+        assert_eq!((first.font_size, first.background.is_some()), (None, false));
+        let second = app.world().resource::<ScreenDiagnostics<Second>>();
+        assert_eq!(
+            (second.font_size, second.background.is_some()),
+            (Some(30.0), false)
+        );
+        // the default theme of the second panel doesn't replace the first one
+        let theme = app.world().resource::<ScreenDiagnosticsTheme>();
+        assert_eq!(theme, &ScreenDiagnosticsTheme::dark());
+    }
+
+    #[test]
+    fn respawn_despawned_panel() {
+        type Panel = DiagnosticsPanelMarker<DefaultDiagnosticsPanel>;
+
+        let mut world = World::new();
+        world.init_resource::<ScreenDiagnostics>();
+        world
+            .run_system_once(spawn_ui::<DefaultDiagnosticsPanel>)
+            .unwrap();
+        let panel = world
+            .query_filtered::<Entity, With<Panel>>()
+            .single(&world)
+            .unwrap();
+
+        // not spawned twice
+        world.resource_mut::<ScreenDiagnostics>().layout_changed = false;
+        world
+            .run_system_once(spawn_ui::<DefaultDiagnosticsPanel>)
+            .unwrap();
+        assert!(!world.resource::<ScreenDiagnostics>().layout_changed);
+
+        world.despawn(panel);
+        world
+            .run_system_once(spawn_ui::<DefaultDiagnosticsPanel>)
+            .unwrap();
+        let panels = world
+            .query_filtered::<(), With<Panel>>()
+            .iter(&world)
+            .count();
+        assert_eq!(panels, 1);
+        assert!(world.resource::<ScreenDiagnostics>().layout_changed);
+    }
+
+    #[test]
+    fn table_is_one_grid_for_all_groups() {
+        let mut world = World::new();
+        world.init_resource::<ScreenDiagnosticsTheme>();
+        let mut diags: ScreenDiagnostics = ScreenDiagnostics::default();
+        diags.set_layout(DiagnosticsLayout::Table { value_width: 50.0 });
+        diags.add("a", PATH).group("first");
+        diags.add("b", PATH).group("second");
+        world.insert_resource(diags);
+        world
+            .run_system_once(spawn_ui::<DefaultDiagnosticsPanel>)
+            .unwrap();
+        world
+            .run_system_once(update_onscreen_diags_layout::<DefaultDiagnosticsPanel>)
+            .unwrap();
+
+        let grids: Vec<Entity> = world
+            .query::<(Entity, &Node)>()
+            .iter(&world)
+            .filter(|(_, node)| node.display == Display::Grid)
+            .map(|(entity, _)| entity)
+            .collect();
+        assert_eq!(grids.len(), 1);
+        // both headers and the three cells of both rows
+        let cells = world.get::<Children>(grids[0]).unwrap();
+        assert_eq!(cells.len(), 8);
+        let headers = cells
+            .iter()
+            .filter(|cell| world.get::<Node>(*cell).unwrap().grid_column == GridPlacement::span(3))
+            .count();
+        assert_eq!(headers, 2);
+    }
+
+    #[test]
+    fn move_to_replaces_insertion_order() {
+        let mut diags = ScreenDiagnostics::default();
+        diags.add("a", PATH);
+        diags.add("b", PATH);
+        diags.add("c", PATH);
+        diags.move_to("c", 0);
+        assert_eq!(names(&diags), ["c", "a", "b"]);
+        diags.move_to("a", 10);
+        assert_eq!(names(&diags), ["c", "b", "a"]);
+        diags.add("d", PATH);
+        assert_eq!(names(&diags), ["c", "b", "a", "d"]);
     }
-    diag.layout_changed = layout_changed;
-    Ok(())
 }
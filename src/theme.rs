@@ -0,0 +1,162 @@
+use bevy::{color::palettes::css, prelude::*};
+
+/// The panel displayed behind the diagnostics, part of the [ScreenDiagnosticsTheme].
+///
+/// Can be set for the diagnostics of a single plugin with [ScreenDiagnostics::set_background](crate::ScreenDiagnostics::set_background).
+///
+/// ```rust
+///# use bevy::prelude::*;
+///# use bevy_screen_diagnostics::{DiagnosticsBackground, ScreenDiagnosticsPlugin, ScreenDiagnosticsTheme};
+///
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .add_plugins(ScreenDiagnosticsPlugin {
+///         theme: ScreenDiagnosticsTheme {
+///             background: DiagnosticsBackground {
+///                 color: Color::srgba(0.0, 0.0, 0.0, 0.6),
+///                 padding: UiRect::all(Val::Px(6.0)),
+///                 border_radius: BorderRadius::all(Val::Px(4.0)),
+///                 ..default()
+///             },
+///             ..default()
+///         },
+///         ..default()
+///     });
+/// ```
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub struct DiagnosticsBackground {
+    /// The background color, its alpha sets the opacity. Default: [Color::NONE]
+    pub color: Color,
+    /// The space between the border and the diagnostics. Default: none
+    pub padding: UiRect,
+    /// The width of the border. Default: none
+    pub border: UiRect,
+    /// The color of the border. Default: [Color::NONE]
+    pub border_color: Color,
+    /// The radius of the corners. Default: none
+    pub border_radius: BorderRadius,
+}
+
+impl Default for DiagnosticsBackground {
+    fn default() -> Self {
+        Self {
+            color: Color::NONE,
+            padding: UiRect::DEFAULT,
+            border: UiRect::DEFAULT,
+            border_color: Color::NONE,
+            border_radius: BorderRadius::DEFAULT,
+        }
+    }
+}
+
+impl DiagnosticsBackground {
+    /// A translucent panel with some padding and rounded corners.
+    fn panel(color: Color) -> Self {
+        Self {
+            color,
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+            border_radius: BorderRadius::all(Val::Px(4.0)),
+            ..default()
+        }
+    }
+}
+
+/// Resource with the default styling of all diagnostics.
+///
+/// Colors set with [DiagnosticsTextBuilder::diagnostic_color](crate::DiagnosticsTextBuilder::diagnostic_color)
+/// or [DiagnosticsTextBuilder::name_color](crate::DiagnosticsTextBuilder::name_color) take precedence,
+/// all other diagnostics are restyled when the theme changes.
+///
+/// ```rust
+///# use bevy::prelude::*;
+///# use bevy_screen_diagnostics::ScreenDiagnosticsTheme;
+///
+/// fn light_mode(mut theme: ResMut<ScreenDiagnosticsTheme>) {
+///     *theme = ScreenDiagnosticsTheme::light();
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+pub struct ScreenDiagnosticsTheme {
+    /// The color of the diagnostic values.
+    pub value_color: Color,
+    /// The color of the diagnostic names and group headers.
+    pub name_color: Color,
+    /// The font size of the text.
+    pub font_size: f32,
+    /// The panel behind the diagnostics.
+    pub background: DiagnosticsBackground,
+    /// The colors from good to bad used by [DiagnosticsTextBuilder::palette_thresholds](crate::DiagnosticsTextBuilder::palette_thresholds)
+    /// and [DiagnosticsTextBuilder::palette_gradient](crate::DiagnosticsTextBuilder::palette_gradient).
+    pub palette: Vec<Color>,
+}
+
+/// Red values and white names without a background.
+impl Default for ScreenDiagnosticsTheme {
+    fn default() -> Self {
+        Self {
+            value_color: css::RED.into(),
+            name_color: css::WHITE.into(),
+            font_size: 20.0,
+            background: DiagnosticsBackground::default(),
+            palette: vec![css::LIME.into(), css::YELLOW.into(), css::RED.into()],
+        }
+    }
+}
+
+impl ScreenDiagnosticsTheme {
+    /// Light text on a translucent dark panel.
+    pub fn dark() -> Self {
+        Self {
+            value_color: css::GOLD.into(),
+            name_color: css::WHITE_SMOKE.into(),
+            background: DiagnosticsBackground::panel(Color::srgba(0.05, 0.05, 0.05, 0.75)),
+            ..default()
+        }
+    }
+
+    /// Dark text on a translucent light panel.
+    pub fn light() -> Self {
+        Self {
+            value_color: css::DARK_RED.into(),
+            name_color: css::BLACK.into(),
+            background: DiagnosticsBackground::panel(Color::srgba(0.95, 0.95, 0.95, 0.85)),
+            palette: vec![
+                css::GREEN.into(),
+                css::DARK_ORANGE.into(),
+                css::CRIMSON.into(),
+            ],
+            ..default()
+        }
+    }
+
+    /// Larger, saturated text on an opaque black panel with a white border.
+    pub fn high_contrast() -> Self {
+        Self {
+            value_color: css::YELLOW.into(),
+            name_color: css::WHITE.into(),
+            font_size: 24.0,
+            background: DiagnosticsBackground {
+                border: UiRect::all(Val::Px(2.0)),
+                border_color: css::WHITE.into(),
+                ..DiagnosticsBackground::panel(Color::BLACK)
+            },
+            palette: vec![css::AQUA.into(), css::YELLOW.into(), css::FUCHSIA.into()],
+        }
+    }
+
+    /// Colors of the [Okabe-Ito](https://jfly.uni-koeln.de/color/) palette,
+    /// which stay distinguishable with the common forms of color blindness.
+    pub fn color_blind_safe() -> Self {
+        Self {
+            value_color: Color::srgb_u8(240, 228, 66),
+            name_color: css::WHITE.into(),
+            background: DiagnosticsBackground::panel(Color::srgba(0.05, 0.05, 0.05, 0.75)),
+            palette: vec![
+                Color::srgb_u8(86, 180, 233),
+                Color::srgb_u8(230, 159, 0),
+                Color::srgb_u8(213, 94, 0),
+            ],
+            ..default()
+        }
+    }
+}
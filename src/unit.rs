@@ -0,0 +1,147 @@
+use std::fmt::{self, Write};
+
+use bevy::prelude::*;
+
+/// Units for displaying diagnostic values, see [DiagnosticsTextBuilder::unit](crate::DiagnosticsTextBuilder::unit).
+///
+/// Values are scaled to a fitting prefix and shown with 3 significant digits,
+/// for example ``0.25`` [Unit::Milliseconds] is shown as ``250us``.
+/// Only ASCII is used, as the built-in font does not contain other characters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum Unit {
+    /// Milliseconds, scaled to us, ms or s.
+    Milliseconds,
+    /// Seconds, scaled to us, ms or s.
+    Seconds,
+    /// Bytes, scaled to B, KiB, MiB, GiB or TiB.
+    Bytes,
+    /// Percent, not scaled.
+    Percent,
+    /// A plain amount, scaled to k, M, G or T.
+    Count,
+    /// Hertz, scaled to Hz, kHz, MHz or GHz.
+    Hertz,
+}
+
+const TIME_PREFIXES: &[(f64, &str)] = &[(1.0, "s"), (1e-3, "ms"), (1e-6, "us")];
+const BINARY_PREFIXES: &[(f64, &str)] = &[
+    (1024.0 * 1024.0 * 1024.0 * 1024.0, "TiB"),
+    (1024.0 * 1024.0 * 1024.0, "GiB"),
+    (1024.0 * 1024.0, "MiB"),
+    (1024.0, "KiB"),
+    (1.0, "B"),
+];
+const COUNT_PREFIXES: &[(f64, &str)] =
+    &[(1e12, "T"), (1e9, "G"), (1e6, "M"), (1e3, "k"), (1.0, "")];
+const HERTZ_PREFIXES: &[(f64, &str)] = &[(1e9, "GHz"), (1e6, "MHz"), (1e3, "kHz"), (1.0, "Hz")];
+
+impl Unit {
+    /// The unit matching a [Diagnostic::suffix](bevy::diagnostic::Diagnostic::suffix), if there is one.
+    ///
+    /// Prefixed suffixes like `GiB` don't match, as their values are not in the unit itself.
+    /// Without a unit or format set, diagnostics with `KiB`, `MiB`, `GiB` or `TiB` are still
+    /// displayed as [Unit::Bytes], scaled to bytes first.
+    ///
+    /// ```rust
+    ///# use bevy_screen_diagnostics::Unit;
+    /// assert_eq!(Unit::from_suffix("ms"), Some(Unit::Milliseconds));
+    /// assert_eq!(Unit::from_suffix("GiB"), None);
+    /// ```
+    pub fn from_suffix(suffix: &str) -> Option<Unit> {
+        match suffix.trim() {
+            "ms" => Some(Unit::Milliseconds),
+            "s" => Some(Unit::Seconds),
+            "B" | "bytes" => Some(Unit::Bytes),
+            "%" => Some(Unit::Percent),
+            "Hz" => Some(Unit::Hertz),
+            _ => None,
+        }
+    }
+
+    /// The unit matching a suffix, with the factor scaling the values to it, like `GiB` to bytes.
+    pub(crate) fn from_scaled_suffix(suffix: &str) -> Option<(Unit, f64)> {
+        let suffix = suffix.trim();
+        match BINARY_PREFIXES.iter().find(|(_, prefix)| *prefix == suffix) {
+            Some((factor, _)) => Some((Unit::Bytes, *factor)),
+            None => Unit::from_suffix(suffix).map(|unit| (unit, 1.0)),
+        }
+    }
+
+    /// Format a value of this unit, scaled to a fitting prefix.
+    ///
+    /// Values which would round up to 1000 use the next larger prefix,
+    /// values too small for any prefix, like zero, use the prefix of the unit itself.
+    ///
+    /// ```rust
+    ///# use bevy_screen_diagnostics::Unit;
+    /// assert_eq!(Unit::Milliseconds.format(0.0), "0.00ms");
+    /// assert_eq!(Unit::Seconds.format(0.0), "0.00s");
+    /// assert_eq!(Unit::Milliseconds.format(0.25), "250us");
+    /// assert_eq!(Unit::Milliseconds.format(999.4), "999ms");
+    /// assert_eq!(Unit::Milliseconds.format(999.5), "1.00s");
+    /// assert_eq!(Unit::Milliseconds.format(1000.0), "1.00s");
+    /// assert_eq!(Unit::Hertz.format(60.0), "60.0Hz");
+    /// assert_eq!(Unit::Milliseconds.format(-2.5), "-2.50ms");
+    /// assert_eq!(Unit::Bytes.format(512.0), "512B");
+    /// assert_eq!(Unit::Bytes.format(5.0), "5B");
+    /// assert_eq!(Unit::Bytes.format(1024.0), "1.00KiB");
+    /// assert_eq!(Unit::Count.format(-1500.0), "-1.50k");
+    /// assert_eq!(Unit::Count.format(7.0), "7");
+    /// ```
+    pub fn format(&self, v: f64) -> String {
+        let mut s = String::new();
+        // writing to a String can't fail
+        let _ = self.format_into(v, &mut s);
+        s
+    }
+
+    /// Write a value of this unit, scaled to a fitting prefix, without allocating.
+    pub fn format_into(&self, v: f64, w: &mut impl Write) -> fmt::Result {
+        match self {
+            Unit::Milliseconds => scaled(w, v / 1000.0, TIME_PREFIXES, (1e-3, "ms"), false),
+            Unit::Seconds => scaled(w, v, TIME_PREFIXES, (1.0, "s"), false),
+            Unit::Bytes => scaled(w, v, BINARY_PREFIXES, (1.0, "B"), true),
+            Unit::Percent => {
+                significant(w, v)?;
+                w.write_char('%')
+            }
+            Unit::Count => scaled(w, v, COUNT_PREFIXES, (1.0, ""), true),
+            Unit::Hertz => scaled(w, v, HERTZ_PREFIXES, (1.0, "Hz"), false),
+        }
+    }
+}
+
+/// Scale the value by the largest prefix it reaches, or the `base` prefix of the unit.
+///
+/// Units counting whole things, like bytes, are written without decimals when unscaled.
+fn scaled(
+    w: &mut impl Write,
+    v: f64,
+    prefixes: &[(f64, &str)],
+    base: (f64, &'static str),
+    whole: bool,
+) -> fmt::Result {
+    let (factor, prefix) = prefixes
+        .iter()
+        // from 0.9995 the value is rounded to 1000 of the smaller prefix
+        .find(|(factor, _)| v.abs() >= *factor * 0.9995)
+        .copied()
+        .unwrap_or(base);
+    let v = v / factor;
+    match whole && factor == 1.0 && v.fract() == 0.0 {
+        // whole unscaled counts or bytes don't need decimals
+        true => write!(w, "{v:.0}")?,
+        false => significant(w, v)?,
+    }
+    w.write_str(prefix)
+}
+
+/// Write with 3 significant digits for values up to 1000.
+fn significant(w: &mut impl Write, v: f64) -> fmt::Result {
+    let precision = match v.abs() {
+        v if v < 10.0 => 2,
+        v if v < 100.0 => 1,
+        _ => 0,
+    };
+    write!(w, "{v:.precision$}")
+}
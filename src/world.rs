@@ -0,0 +1,306 @@
+use std::time::Duration;
+
+use bevy::{
+    diagnostic::DiagnosticPath,
+    ecs::{component::HookContext, world::DeferredWorld},
+    prelude::*,
+    render::view::RenderLayers,
+    text::LineHeight,
+    time::common_conditions::on_timer,
+    transform::TransformSystem,
+    ui::{DefaultUiCamera, UiSystem},
+};
+
+use crate::{
+    DiagnosticsLayout, DiagnosticsText, DiagnosticsTextBuilder, DiagnosticsUpdater,
+    ScreenDiagnosticsTheme, add_text, sort_for_display, spawn_row,
+};
+
+/// Component which displays diagnostics in a label floating above its entity.
+///
+/// The label follows the [GlobalTransform] of the entity and is styled with the [ScreenDiagnosticsTheme].
+/// It is updated with the timestep and uses the font, line height and render layer of the first added
+/// [ScreenDiagnosticsPlugin](crate::ScreenDiagnosticsPlugin).
+/// Diagnostics are added like with [ScreenDiagnostics::add](crate::ScreenDiagnostics::add),
+/// groups are not displayed in the label.
+///
+/// ```rust
+///# use bevy::{diagnostic::DiagnosticPath, prelude::*};
+///# use bevy_screen_diagnostics::{Aggregate, EntityDiagnostics, Unit};
+///
+/// const PATH_COST: DiagnosticPath = DiagnosticPath::const_new("agent/path_cost");
+///
+/// fn spawn_agent(mut commands: Commands) {
+///     let mut diagnostics = EntityDiagnostics::default().with_offset(Vec3::Y * 2.0);
+///     diagnostics
+///         .add("path", PATH_COST)
+///         .aggregate(Aggregate::Average)
+///         .unit(Unit::Milliseconds);
+///     commands.spawn((Transform::default(), diagnostics));
+/// }
+/// ```
+#[derive(Component)]
+#[component(on_remove = despawn_label)]
+pub struct EntityDiagnostics {
+    diagnostics: Vec<DiagnosticsText>,
+    offset: Vec3,
+    camera: Option<Entity>,
+    layout_changed: bool,
+    label: Option<Entity>,
+}
+
+impl Default for EntityDiagnostics {
+    fn default() -> Self {
+        Self {
+            diagnostics: Vec::new(),
+            offset: Vec3::ZERO,
+            camera: None,
+            layout_changed: false,
+            label: None,
+        }
+    }
+}
+
+impl EntityDiagnostics {
+    /// Display the label this far away from the entity, in world space. Default: [Vec3::ZERO]
+    pub fn with_offset(mut self, offset: Vec3) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Display the label on the camera, instead of the default UI camera.
+    pub fn with_camera(mut self, camera: Entity) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    /// Add a diagnostic to be displayed in the label.
+    ///
+    /// Uses the same syntax as [ScreenDiagnostics::add](crate::ScreenDiagnostics::add)
+    pub fn add<S>(&mut self, name: S, path: DiagnosticPath) -> DiagnosticsTextBuilder<'_>
+    where
+        S: Into<String>,
+    {
+        add_text(&mut self.diagnostics, name.into(), path)
+    }
+
+    /// Modify a diagnostic of the label by name.
+    pub fn modify<S>(&mut self, name: S) -> DiagnosticsTextBuilder<'_>
+    where
+        S: Into<String>,
+    {
+        DiagnosticsTextBuilder {
+            m: &mut self.diagnostics,
+            k: name.into(),
+        }
+    }
+
+    /// Remove a diagnostic from the label by name.
+    pub fn remove(&mut self, name: String) {
+        self.diagnostics.retain(|e| e.name != name);
+        self.layout_changed = true;
+    }
+
+    /// Set the distance of the label from the entity, in world space.
+    pub fn set_offset(&mut self, offset: Vec3) {
+        self.offset = offset;
+    }
+
+    /// Display the label on the camera, or the default UI camera when `None`.
+    pub fn set_camera(&mut self, camera: Option<Entity>) {
+        self.camera = camera;
+    }
+}
+
+/// The label displaying the [EntityDiagnostics] of an entity.
+#[derive(Component)]
+#[require(Node)]
+struct EntityDiagnosticsLabel;
+
+/// The labels are separate entities, so they have to be despawned with the component.
+fn despawn_label(mut world: DeferredWorld, context: HookContext) {
+    if let Some(label) = world
+        .get::<EntityDiagnostics>(context.entity)
+        .and_then(|diags| diags.label)
+    {
+        world.commands().entity(label).try_despawn();
+    }
+}
+
+/// The font and render layer of the labels, taken from the first [ScreenDiagnosticsPlugin](crate::ScreenDiagnosticsPlugin).
+#[derive(Resource)]
+struct EntityDiagnosticsFont {
+    font: Handle<Font>,
+    line_height: LineHeight,
+    render_layer: RenderLayers,
+}
+
+/// Adds the systems for [EntityDiagnostics], once for all panels.
+pub(crate) struct EntityDiagnosticsPlugin {
+    pub(crate) timestep: f64,
+    pub(crate) font: Handle<Font>,
+    pub(crate) line_height: LineHeight,
+    pub(crate) render_layer: RenderLayers,
+}
+
+impl Plugin for EntityDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(EntityDiagnosticsFont {
+            font: self.font.clone(),
+            line_height: self.line_height,
+            render_layer: self.render_layer.clone(),
+        })
+        .add_systems(
+            Update,
+            (update_labels, update_entity_diags)
+                .chain()
+                .run_if(on_timer(Duration::from_secs_f64(self.timestep))),
+        )
+        .add_systems(
+            PostUpdate,
+            position_labels
+                .after(TransformSystem::TransformPropagate)
+                .before(UiSystem::Layout),
+        );
+    }
+}
+
+fn update_labels(
+    mut entities: Query<&mut EntityDiagnostics>,
+    font: Res<EntityDiagnosticsFont>,
+    theme: Res<ScreenDiagnosticsTheme>,
+    mut commands: Commands,
+) {
+    let text_font = TextFont {
+        font: font.font.clone(),
+        font_size: theme.font_size,
+        line_height: font.line_height,
+        ..default()
+    };
+    let layer = &font.render_layer;
+    let background = &theme.background;
+
+    for mut diags in &mut entities {
+        // rebuild on theme changes, to restyle everything which isn't overridden
+        if !(diags.layout_changed || theme.is_changed()) {
+            continue;
+        }
+        let diags = &mut *diags;
+        // spawn the label again if it was despawned, like with a state scoped UI
+        let label = match diags
+            .label
+            .filter(|label| commands.get_entity(*label).is_ok())
+        {
+            Some(label) => {
+                commands.entity(label).despawn_related::<Children>();
+                label
+            }
+            // hidden until it is positioned
+            None => *diags.label.insert(
+                commands
+                    .spawn((EntityDiagnosticsLabel, Visibility::Hidden, layer.clone()))
+                    .id(),
+            ),
+        };
+
+        commands
+            .entity(label)
+            .insert((
+                Node {
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: background.padding,
+                    border: background.border,
+                    ..default()
+                },
+                BackgroundColor(background.color),
+                BorderColor(background.border_color),
+                background.border_radius,
+            ))
+            .with_children(|rows| {
+                sort_for_display(&mut diags.diagnostics);
+                for text in diags.diagnostics.iter_mut() {
+                    text.entities = text.show.then(|| {
+                        spawn_row(
+                            rows,
+                            text,
+                            &text_font,
+                            &theme,
+                            layer,
+                            DiagnosticsLayout::Inline,
+                        )
+                    });
+                }
+            });
+
+        diags.layout_changed = false;
+    }
+}
+
+fn update_entity_diags(
+    mut entities: Query<&mut EntityDiagnostics>,
+    mut updater: DiagnosticsUpdater,
+) {
+    for mut diags in &mut entities {
+        if diags.layout_changed {
+            continue;
+        }
+        diags.layout_changed = updater.update(&mut diags.diagnostics);
+    }
+}
+
+/// Move the labels above their entities, every frame so they don't lag behind.
+fn position_labels(
+    entities: Query<(&EntityDiagnostics, &GlobalTransform)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    default_camera: DefaultUiCamera,
+    ui_scale: Res<UiScale>,
+    mut labels: Query<
+        (
+            &mut Node,
+            &mut Visibility,
+            &ComputedNode,
+            Option<&UiTargetCamera>,
+        ),
+        With<EntityDiagnosticsLabel>,
+    >,
+    mut commands: Commands,
+) {
+    for (diags, transform) in &entities {
+        let Some(label) = diags.label else {
+            continue;
+        };
+        let Ok((mut node, mut visibility, computed, target)) = labels.get_mut(label) else {
+            continue;
+        };
+        let Some(camera_entity) = diags.camera.or_else(|| default_camera.get()) else {
+            continue;
+        };
+        let Ok((camera, camera_transform)) = cameras.get(camera_entity) else {
+            continue;
+        };
+        if target.map(|t| t.0) != Some(camera_entity) {
+            commands.entity(label).insert(UiTargetCamera(camera_entity));
+        }
+
+        match camera.world_to_viewport(camera_transform, transform.translation() + diags.offset) {
+            Ok(position) => {
+                // centered horizontally, with the bottom edge at the position.
+                // the inverse scale factor includes the ui scale, so the size is in `Val` units
+                let size = computed.size() * computed.inverse_scale_factor();
+                let left = Val::Px(position.x / ui_scale.0 - size.x / 2.0);
+                let top = Val::Px(position.y / ui_scale.0 - size.y);
+                if node.left != left || node.top != top {
+                    node.left = left;
+                    node.top = top;
+                }
+                visibility.set_if_neq(Visibility::Inherited);
+            }
+            // behind the camera
+            Err(_) => {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+        }
+    }
+}